@@ -1,13 +1,24 @@
-use rustc_hash::FxHashMap;
-use typst_syntax::{ast, Span, SyntaxKind, SyntaxNode};
+use rustc_hash::{FxHashMap, FxHashSet};
+use typst_syntax::{
+    ast::{self, AstNode},
+    Span, SyntaxKind, SyntaxNode,
+};
 
-use crate::ext::StrExt;
+use crate::{ext::StrExt, Config};
 
 #[derive(Debug, Clone, Default)]
 pub struct Attributes {
-    /// Indicates whether formatting is explicitly disabled (`@typstyle off`) or always ignored.
+    /// Indicates whether formatting is explicitly disabled (`@typstyle off`) or always ignored,
+    /// such as for erroneous nodes in error-tolerant formatting.
     pub(self) is_format_disabled: bool,
 
+    /// The number of following siblings in the region disabled by this `@typstyle off`
+    /// comment, up to and including the matching `@typstyle on` comment.
+    pub(self) disabled_region_len: Option<usize>,
+
+    /// Changes to the formatting of the node, given by a `@typstyle` directive before it.
+    pub(self) overrides: Option<Box<Overrides>>,
+
     /// Indicates whether any child node contains a comment.
     pub(self) has_comment: bool,
 
@@ -24,13 +35,72 @@ pub struct Attributes {
     /// Indicates whether the node has a multiline "flavor",
     /// determined by the first space child containing a linebreak.
     pub(self) is_multiline_flavor: bool,
+
+    /// The number of an enum item in its sequence, if it is in a markup.
+    pub(self) enum_number: Option<EnumNumber>,
+
+    /// Indicates whether an import item binds a name that is never referenced,
+    /// or all items of an import do.
+    pub(self) is_unused_import: bool,
+}
+
+/// The number of an enum item, as Typst counts it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnumNumber {
+    /// The number of the item. Explicit numbers are kept, and other items count up from the
+    /// previous item of the sequence, or from 1.
+    pub number: usize,
+    /// Whether the number is implied by its position, so that it can be written as `+`.
+    pub is_implied: bool,
+}
+
+/// Changes to the formatting of a node, given by a directive like
+/// `// @typstyle max_width=120 skip(table)` before it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Overrides {
+    /// Options to set, as pairs of names and values. See [`Config::set_option`].
+    ///
+    /// [`Config::set_option`]: crate::Config::set_option
+    pub options: Vec<(String, String)>,
+    /// Layout features to skip.
+    pub skipped: Vec<LayoutFeature>,
+}
+
+/// A layout feature that can be skipped with `@typstyle skip(...)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutFeature {
+    /// Aligning the columns of tables and grids.
+    Table,
+    /// Aligning math at align points (`&`).
+    MathAlign,
+}
+
+impl LayoutFeature {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "table" => Some(Self::Table),
+            "math_align" => Some(Self::MathAlign),
+            _ => None,
+        }
+    }
+}
+
+/// A problem with a `@typstyle` directive, such as one that has no effect.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectiveDiagnostic {
+    /// The span of the comment containing the directive.
+    pub span: Span,
+    /// A description of the problem.
+    pub message: String,
 }
 
 /// A storage structure that manages formatting attributes for syntax nodes.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct AttrStore {
     /// A mapping between syntax node spans and their associated attributes.
     attr_map: FxHashMap<Span, Attributes>,
+    /// Problems with directives found while computing the attributes, in source order.
+    diagnostics: Vec<DirectiveDiagnostic>,
 }
 
 impl AttrStore {
@@ -40,13 +110,33 @@ impl AttrStore {
         if node.erroneous() {
             return Default::default(); // No attributes for erroneous nodes
         }
+        Self::compute(node)
+    }
+
+    /// Creates a new `AttrStore` for error-tolerant formatting. Unlike [`AttrStore::new`],
+    /// attributes are also computed if the node has syntax errors, and the erroneous parts are
+    /// disabled, so that they are kept verbatim.
+    pub fn new_tolerant(node: &SyntaxNode) -> AttrStore {
+        let mut store = Self::compute(node);
+        store.compute_erroneous(node);
+        store
+    }
+
+    fn compute(node: &SyntaxNode) -> AttrStore {
         let mut store = AttrStore::default();
         store.compute_no_format(node);
         store.compute_multiline(node);
         store.compute_math_align_point(node);
+        store.compute_enum_numbers(node);
         store
     }
 
+    /// Gets the problems with `@typstyle` directives, such as directives that have no effect,
+    /// unknown directives and `@typstyle on` without a matching `@typstyle off`.
+    pub fn diagnostics(&self) -> &[DirectiveDiagnostic] {
+        &self.diagnostics
+    }
+
     /// Checks if a given syntax node contains a comment.
     pub fn has_comment(&self, node: &SyntaxNode) -> bool {
         self.check_node_attr(node, |attr| attr.has_comment)
@@ -81,12 +171,84 @@ impl AttrStore {
         self.check_node_attr(node, |attr| attr.is_format_disabled)
     }
 
+    /// Gets the number of following siblings in the region started by a `@typstyle off`
+    /// comment, including the `@typstyle on` comment that ends it. The region should be
+    /// kept verbatim as a whole.
+    pub fn disabled_region_len(&self, node: &SyntaxNode) -> Option<usize> {
+        self.attr_map.get(&node.span())?.disabled_region_len
+    }
+
+    /// Gets the changes to the formatting of a node, given by a directive before it.
+    pub fn overrides(&self, node: &SyntaxNode) -> Option<&Overrides> {
+        self.attr_map.get(&node.span())?.overrides.as_deref()
+    }
+
     /// Checks if a node is unformattable, defined as having formatting disabled
     /// or containing a comment.
     pub fn is_unformattable(&self, node: &SyntaxNode) -> bool {
         self.check_node_attr(node, |attr| attr.is_format_disabled || attr.has_comment)
     }
 
+    /// Gets the number of an enum item in its sequence. Returns `None` if the item is not
+    /// in a markup, such as when it is the root.
+    pub fn enum_number(&self, node: &SyntaxNode) -> Option<EnumNumber> {
+        self.attr_map.get(&node.span())?.enum_number
+    }
+
+    /// Checks if a given import item, or all items of an import, are unused.
+    /// Only available after [`AttrStore::compute_unused_imports`].
+    pub fn is_unused_import(&self, node: &SyntaxNode) -> bool {
+        self.check_node_attr(node, |attr| attr.is_unused_import)
+    }
+
+    /// Marks import items whose bound names are never referenced in `root`, and imports all of
+    /// whose items are unused. Wildcard imports and imports with comments are never marked.
+    ///
+    /// It is not computed by [`AttrStore::new`], as it needs the whole source even when
+    /// only a part of it is formatted.
+    pub fn compute_unused_imports(&mut self, root: &SyntaxNode) {
+        if root.erroneous() {
+            return;
+        }
+        let mut referenced = FxHashSet::default();
+        let mut imports = vec![];
+        collect_references(root, &mut referenced, &mut imports);
+        for import in imports {
+            let Some(ast::Imports::Items(items)) = import.imports() else {
+                continue;
+            };
+            if import
+                .to_untyped()
+                .children()
+                .chain(items.to_untyped().children())
+                .any(|child| {
+                    matches!(
+                        child.kind(),
+                        SyntaxKind::LineComment | SyntaxKind::BlockComment
+                    )
+                })
+            {
+                continue;
+            }
+            let mut all_unused = true;
+            for item in items.iter() {
+                let (node, name) = match item {
+                    ast::ImportItem::Simple(path) => (path.to_untyped(), path.name()),
+                    ast::ImportItem::Renamed(renamed) => (renamed.to_untyped(), renamed.new_name()),
+                };
+                if referenced.contains(name.as_str()) {
+                    all_unused = false;
+                } else {
+                    self.attrs_mut_of(node).is_unused_import = true;
+                }
+            }
+            // An import renamed with `as` still binds the module without items.
+            if all_unused && import.new_name().is_none() {
+                self.attrs_mut_of(import.to_untyped()).is_unused_import = true;
+            }
+        }
+    }
+
     fn check_node_attr(&self, node: &SyntaxNode, pred: impl FnOnce(&Attributes) -> bool) -> bool {
         self.attr_map.get(&node.span()).is_some_and(pred)
     }
@@ -139,48 +301,163 @@ impl AttrStore {
     }
 
     fn compute_no_format(&mut self, root: &SyntaxNode) {
-        self.compute_no_format_impl(root);
+        self.compute_no_format_impl(root, true);
     }
 
-    fn compute_no_format_impl(&mut self, node: &SyntaxNode) {
+    fn compute_no_format_impl(&mut self, node: &SyntaxNode, is_root: bool) {
+        // Regions are only supported where items are separated by linebreaks.
+        let supports_region = matches!(node.kind(), SyntaxKind::Markup | SyntaxKind::Code);
+        let children = node.children().as_slice();
         let mut disable_next = false;
+        let mut overrides_next = None;
+        // The comment of the directive that applies to the next item, if any.
+        let mut pending = None;
         let mut commented = false;
-        for child in node.children() {
+        let mut i = 0;
+        while let Some(child) = children.get(i) {
             match child.kind() {
                 SyntaxKind::LineComment | SyntaxKind::BlockComment => {
                     commented = true;
-                    // @typstyle off affects the whole next block
-                    disable_next = child.text().contains("@typstyle off");
+                    if let Some(directive) = pending.take() {
+                        self.report_no_effect(directive);
+                    }
+                    // Directives affect the whole next block, and @typstyle off may also
+                    // affect all items until the next @typstyle on
+                    let mut errors = vec![];
+                    let directive = Directive::parse(child.text(), &mut errors);
+                    for message in errors {
+                        self.report(child, message);
+                    }
+                    match &directive {
+                        Some(Directive::On) => self.report(
+                            child,
+                            "`@typstyle on` without a preceding `@typstyle off`".to_string(),
+                        ),
+                        Some(Directive::OffFile) if !is_root => self.report(
+                            child,
+                            "`@typstyle off-file` has no effect, as it is not at the top level"
+                                .to_string(),
+                        ),
+                        Some(Directive::Off | Directive::Overrides(_)) => pending = Some(child),
+                        _ => {}
+                    }
+                    disable_next = directive == Some(Directive::Off);
+                    overrides_next = match directive {
+                        Some(Directive::Overrides(overrides)) => Some(overrides),
+                        _ => None,
+                    };
+                    let region_len = children[i + 1..]
+                        .iter()
+                        .position(|it| Directive::of(it) == Some(Directive::On))
+                        .map(|pos| pos + 1)
+                        .filter(|_| disable_next && supports_region);
+                    if let Some(len) = region_len {
+                        self.attrs_mut_of(child).disabled_region_len = Some(len);
+                        for it in &children[i + 1..i + len] {
+                            if !it.kind().is_trivia() {
+                                self.attrs_mut_of(it).is_format_disabled = true;
+                            }
+                        }
+                        disable_next = false;
+                        pending = None;
+                        i += len;
+                    }
                 }
                 SyntaxKind::Space | SyntaxKind::Hash => {}
                 SyntaxKind::Code | SyntaxKind::Math if disable_next => {
                     // no format nodes with @typstyle off
-                    self.disable_first_nontrivial_child(child);
+                    if self.disable_first_nontrivial_child(child) {
+                        pending = None;
+                    }
                     disable_next = false;
                 }
                 _ if disable_next => {
                     // no format nodes with @typstyle off
                     if !child.kind().is_trivia() {
                         self.attrs_mut_of(child).is_format_disabled = true;
+                        if !is_closing_delimiter(child) {
+                            pending = None;
+                        }
                     }
                     disable_next = false;
                 }
                 _ => {
+                    if let Some(overrides) = overrides_next.take() {
+                        let target = match child.kind() {
+                            SyntaxKind::Code | SyntaxKind::Math => first_nontrivial_child(child),
+                            _ => Some(child)
+                                .filter(|it| !it.kind().is_trivia() && !is_closing_delimiter(it)),
+                        };
+                        if let Some(target) = target {
+                            self.attrs_mut_of(target).overrides = Some(overrides);
+                            pending = None;
+                        }
+                    }
                     if !child.kind().is_trivia() {
-                        self.compute_no_format_impl(child);
+                        self.compute_no_format_impl(child, false);
                     }
                 }
             }
+            if let Some(directive) = pending.filter(|_| !disable_next && overrides_next.is_none()) {
+                // The directive was consumed by trivia, such as a blank line.
+                self.report_no_effect(directive);
+                pending = None;
+            }
+            i += 1;
+        }
+        if let Some(directive) = pending {
+            self.report_no_effect(directive);
         }
         if commented {
             self.attrs_mut_of(node).has_comment = true;
         }
     }
 
-    fn disable_first_nontrivial_child(&mut self, node: &SyntaxNode) {
-        node.children()
-            .find(|it| !matches!(it.kind(), SyntaxKind::Space | SyntaxKind::Hash))
-            .inspect(|it| self.attrs_mut_of(it).is_format_disabled = true);
+    /// Disables formatting of the first non-trivial child, returning whether there is one.
+    fn disable_first_nontrivial_child(&mut self, node: &SyntaxNode) -> bool {
+        first_nontrivial_child(node)
+            .inspect(|it| self.attrs_mut_of(it).is_format_disabled = true)
+            .is_some()
+    }
+
+    fn report(&mut self, node: &SyntaxNode, message: String) {
+        self.diagnostics.push(DirectiveDiagnostic {
+            span: node.span(),
+            message,
+        });
+    }
+
+    fn report_no_effect(&mut self, node: &SyntaxNode) {
+        self.report(
+            node,
+            "directive has no effect, as it is not directly followed by an item".to_string(),
+        );
+    }
+
+    /// Disables erroneous nodes. Items of markups and code blocks are disabled separately,
+    /// so that those without errors can still be formatted.
+    fn compute_erroneous(&mut self, node: &SyntaxNode) {
+        if !node.erroneous() {
+            return;
+        }
+        let has_separate_items = match node.kind() {
+            SyntaxKind::Markup | SyntaxKind::Code => true,
+            // The delimiters are printed anew, so they must be intact.
+            SyntaxKind::CodeBlock => {
+                is_delimited(node, SyntaxKind::LeftBrace, SyntaxKind::RightBrace)
+            }
+            SyntaxKind::ContentBlock => {
+                is_delimited(node, SyntaxKind::LeftBracket, SyntaxKind::RightBracket)
+            }
+            _ => false,
+        };
+        if has_separate_items {
+            for child in node.children() {
+                self.compute_erroneous(child);
+            }
+        } else {
+            self.attrs_mut_of(node).is_format_disabled = true;
+        }
     }
 
     fn compute_math_align_point(&mut self, root: &SyntaxNode) {
@@ -208,7 +485,262 @@ impl AttrStore {
         }
     }
 
+    fn compute_enum_numbers(&mut self, node: &SyntaxNode) {
+        // Consecutive enum items in a markup form a sequence, even if they are separated by
+        // blank lines or comments.
+        let mut next_number = None;
+        for child in node.children() {
+            match child.kind() {
+                SyntaxKind::EnumItem if node.kind() == SyntaxKind::Markup => {
+                    let item = child.cast::<ast::EnumItem>().expect("enum item");
+                    let implied = next_number.unwrap_or(1);
+                    let number = item.number().unwrap_or(implied);
+                    self.attrs_mut_of(child).enum_number = Some(EnumNumber {
+                        number,
+                        is_implied: number == implied,
+                    });
+                    next_number = Some(number + 1);
+                }
+                SyntaxKind::Space
+                | SyntaxKind::Parbreak
+                | SyntaxKind::LineComment
+                | SyntaxKind::BlockComment => {}
+                _ => next_number = None,
+            }
+            self.compute_enum_numbers(child);
+        }
+    }
+
     fn attrs_mut_of(&mut self, node: &SyntaxNode) -> &mut Attributes {
         self.attr_map.entry(node.span()).or_default()
     }
 }
+
+/// A directive in a comment, such as `// @typstyle off`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Directive {
+    /// Disables formatting of the next item, or all items until `@typstyle on`.
+    Off,
+    /// Ends a region started by `@typstyle off`.
+    On,
+    /// Disables formatting of the whole file.
+    OffFile,
+    /// Changes the formatting of the next item.
+    Overrides(Box<Overrides>),
+}
+
+impl Directive {
+    /// Gets the directive in a comment node, ignoring problems in its arguments.
+    fn of(node: &SyntaxNode) -> Option<Self> {
+        match node.kind() {
+            SyntaxKind::LineComment | SyntaxKind::BlockComment => {
+                Self::parse(node.text(), &mut vec![])
+            }
+            _ => None,
+        }
+    }
+
+    /// Parses the directive in the text of a comment, which must start with `@typstyle`.
+    /// Problems, such as unknown directives or invalid options, are pushed to `errors`.
+    fn parse(comment: &str, errors: &mut Vec<String>) -> Option<Self> {
+        let text = match comment.strip_prefix("//") {
+            Some(text) => text.trim_start_matches('/'),
+            None => comment
+                .strip_prefix("/*")?
+                .strip_suffix("*/")?
+                .trim_start_matches('*'),
+        };
+        let args = text.trim_start().strip_prefix("@typstyle")?;
+        if !args.starts_with(char::is_whitespace) {
+            return None;
+        }
+        match args.split_whitespace().next()? {
+            "off" => Some(Self::Off),
+            "on" => Some(Self::On),
+            "off-file" => Some(Self::OffFile),
+            _ => Self::parse_overrides(args, errors)
+                .map(|overrides| Self::Overrides(Box::new(overrides))),
+        }
+    }
+
+    /// Parses arguments like `max_width=120 skip(table, math_align)`.
+    fn parse_overrides(args: &str, errors: &mut Vec<String>) -> Option<Overrides> {
+        let mut overrides = Overrides::default();
+        let mut rest = args.trim_start();
+        while !rest.is_empty() {
+            if let Some(inner) = rest.strip_prefix("skip(") {
+                let Some((names, after)) = inner.split_once(')') else {
+                    errors.push("unclosed `skip(`".to_string());
+                    break;
+                };
+                for name in names.split(',').map(str::trim) {
+                    match LayoutFeature::from_name(name) {
+                        Some(feature) => overrides.skipped.push(feature),
+                        None => errors.push(format!("unknown layout feature `{name}`")),
+                    }
+                }
+                rest = after;
+            } else {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                let (arg, after) = rest.split_at(end);
+                match arg.split_once('=') {
+                    // Values are checked here, as they do not depend on other options.
+                    Some((key, value)) => match Config::default().set_option(key, value) {
+                        Ok(()) => overrides.options.push((key.to_string(), value.to_string())),
+                        Err(err) => errors.push(err),
+                    },
+                    None => errors.push(format!("unknown directive `{arg}`")),
+                }
+                rest = after;
+            }
+            rest = rest.trim_start();
+        }
+        (!overrides.options.is_empty() || !overrides.skipped.is_empty()).then_some(overrides)
+    }
+}
+
+/// Checks whether formatting of the whole file is disabled with `@typstyle off-file`,
+/// which may be put in any comment at the top level.
+pub fn is_file_format_disabled(root: &SyntaxNode) -> bool {
+    root.children()
+        .any(|child| Directive::of(child) == Some(Directive::OffFile))
+}
+
+/// Checks whether a node starts and ends with the given delimiters.
+fn is_delimited(node: &SyntaxNode, left: SyntaxKind, right: SyntaxKind) -> bool {
+    node.children().next().is_some_and(|it| it.kind() == left)
+        && node.children().last().is_some_and(|it| it.kind() == right)
+}
+
+/// Checks whether a node closes a block, so that it does not follow a directive before it.
+fn is_closing_delimiter(node: &SyntaxNode) -> bool {
+    matches!(
+        node.kind(),
+        SyntaxKind::RightBrace | SyntaxKind::RightBracket | SyntaxKind::RightParen
+    )
+}
+
+fn first_nontrivial_child(node: &SyntaxNode) -> Option<&SyntaxNode> {
+    node.children()
+        .find(|it| !matches!(it.kind(), SyntaxKind::Space | SyntaxKind::Hash))
+}
+
+/// Collects the names of all identifiers outside the bindings of imports, and all imports.
+/// Names bound by other constructs, such as `let`, are also collected to stay conservative.
+fn collect_references<'a>(
+    node: &'a SyntaxNode,
+    referenced: &mut FxHashSet<&'a str>,
+    imports: &mut Vec<ast::ModuleImport<'a>>,
+) {
+    match node.kind() {
+        SyntaxKind::Ident | SyntaxKind::MathIdent => {
+            referenced.insert(node.text().as_str());
+        }
+        SyntaxKind::ModuleImport => {
+            let import = node.cast::<ast::ModuleImport>().expect("module import");
+            imports.push(import);
+            // Only the source refers to names, while the rest binds them.
+            collect_references(import.source().to_untyped(), referenced, imports);
+        }
+        _ => {
+            for child in node.children() {
+                collect_references(child, referenced, imports);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use typst_syntax::Source;
+
+    use super::*;
+
+    fn diagnostics(text: &str) -> Vec<(usize, String)> {
+        let source = Source::detached(text);
+        AttrStore::new(source.root())
+            .diagnostics()
+            .iter()
+            .map(|diag| {
+                let start = source.range(diag.span).unwrap().start;
+                (
+                    source.byte_to_line(start).unwrap() + 1,
+                    diag.message.clone(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_effective_directives() {
+        assert!(diagnostics("// @typstyle off\n#let a  =  1").is_empty());
+        assert!(diagnostics("// @typstyle off\n#let a = 1\n// @typstyle on").is_empty());
+        assert!(diagnostics("#{\n  // @typstyle off\n  let a  =  1\n}").is_empty());
+        assert!(diagnostics("// @typstyle max_width=120 skip(table)\n#table()").is_empty());
+        assert!(diagnostics("// @typstyle off-file\n#let a  =  1").is_empty());
+        assert!(diagnostics("// not a directive\n#let a = 1").is_empty());
+        assert!(diagnostics("// see @typstyle docs\n#let a = 1").is_empty());
+        assert!(diagnostics("/* see @typstyle off */\n\ntext").is_empty());
+        assert!(diagnostics("// @typstyle-like\n#let a = 1").is_empty());
+    }
+
+    #[test]
+    fn test_directives_without_effect() {
+        let no_effect = "directive has no effect, as it is not directly followed by an item";
+        assert_eq!(
+            diagnostics("// @typstyle off\n\ntext"),
+            [(1, no_effect.to_string())]
+        );
+        assert_eq!(
+            diagnostics("// @typstyle off\n// comment\n#let a = 1"),
+            [(1, no_effect.to_string())]
+        );
+        assert_eq!(
+            diagnostics("#{\n  let a = 1\n  // @typstyle max_width=40\n}"),
+            [(3, no_effect.to_string())]
+        );
+        assert_eq!(
+            diagnostics("#[\n  // @typstyle off-file\n]"),
+            [(
+                2,
+                "`@typstyle off-file` has no effect, as it is not at the top level".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_invalid_directives() {
+        assert_eq!(
+            diagnostics("// @typstyle on\n// @typstyle enable\n// @typstyle skip(tables, math_align) width=1\n$a$"),
+            [
+                (1, "`@typstyle on` without a preceding `@typstyle off`".to_string()),
+                (2, "unknown directive `enable`".to_string()),
+                (3, "unknown layout feature `tables`".to_string()),
+                (3, "unknown option `width`".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_enum_numbers() {
+        let source = Source::detached("5. a\n+ b\n\n// comment\n1. c\n7. d\ntext\n+ e\n  + f\n");
+        let store = AttrStore::new(source.root());
+        let mut numbers = vec![];
+        let mut stack = vec![source.root()];
+        while let Some(node) = stack.pop() {
+            numbers.extend(store.enum_number(node).map(|n| (n.number, n.is_implied)));
+            stack.extend(node.children().rev());
+        }
+        assert_eq!(
+            numbers,
+            [
+                (5, false),
+                (6, true),
+                (1, false),
+                (7, false),
+                (1, true),
+                (1, true)
+            ]
+        );
+    }
+}
@@ -1,31 +1,302 @@
+use std::str::FromStr;
+
 /// Configuration Options for Typstyle Printer.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
 pub struct Config {
     /// Number of spaces to use for each indentation level.
+    /// With tab indentation, it is the width of a tab.
     pub tab_spaces: usize,
+    /// Whether to indent with spaces or tabs.
+    pub indent_style: IndentStyle,
+    /// The line ending to use in the output.
+    pub newline_style: NewlineStyle,
     /// Maximum width of each line.
     pub max_width: usize,
+    /// When to add trailing commas to arrays, dictionaries, arguments and parameters.
+    pub trailing_comma: TrailingComma,
+    /// How to write the markers of enum items.
+    pub enum_marker: EnumMarker,
     /// Maximum number of consecutive blank lines allowed between code items.
     pub blank_lines_upper_bound: usize,
+    /// Number of blank lines before headings in markup, where there are blank lines in the
+    /// source. Kept as in the source if unset.
+    pub blank_lines_before_heading: Option<usize>,
+    /// Number of blank lines after headings in markup, where there are blank lines in the
+    /// source. Kept as in the source if unset.
+    pub blank_lines_after_heading: Option<usize>,
+    /// Number of blank lines around block equations, raw blocks and figures in markup, where
+    /// there are blank lines in the source. Kept as in the source if unset.
+    pub blank_lines_around_block: Option<usize>,
     /// When `true`, consecutive whitespace in markup is collapsed into a single space.
     pub collapse_markup_spaces: bool,
     /// When `true`, import items are sorted alphabetically.
     pub reorder_import_items: bool,
-    /// When `true`, text in markup will be wrapped to fit within `max_width`.
-    /// Implies `collapse_markup_spaces`.
-    pub wrap_text: bool,
+    /// When `true`, consecutive top-level import statements are sorted, with packages first,
+    /// and duplicate imports of the same module are merged.
+    pub reorder_import_statements: bool,
+    /// When `true`, import items whose names are never referenced in the file are removed,
+    /// along with imports left without items. Wildcard imports are kept. As top-level imports
+    /// are visible to files importing this one, files that re-export names should not enable it.
+    pub remove_unused_imports: bool,
+    /// How text in markup is wrapped. `true` and `false` are accepted for `fill` and `off`.
+    /// Implies `collapse_markup_spaces` when not `off`.
+    pub wrap_text: WrapText,
+    /// Words ending with a period that do not end a sentence, when `wrap_text` is `sentence`.
+    pub sentence_abbreviations: Vec<String>,
+    /// When `true`, sources with syntax errors are formatted, keeping the erroneous parts
+    /// verbatim, instead of failing with [`Error::SyntaxError`](crate::Error::SyntaxError).
+    pub tolerate_errors: bool,
+}
+
+/// The characters used for indentation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum IndentStyle {
+    /// Indent with `tab_spaces` spaces per level.
+    #[default]
+    Space,
+    /// Indent with one tab per level. Alignment beyond the indentation still uses spaces.
+    Tab,
+}
+
+impl FromStr for IndentStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "space" => Ok(Self::Space),
+            "tab" => Ok(Self::Tab),
+            _ => Err(format!(
+                "invalid indent style: {s}, expected `space` or `tab`"
+            )),
+        }
+    }
+}
+
+/// The line ending used in the output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum NewlineStyle {
+    /// Use the line ending of the first line of the input, or `\n` if there is none.
+    #[default]
+    Auto,
+    /// Always use `\n`.
+    Lf,
+    /// Always use `\r\n`.
+    Crlf,
+    /// Use `\r\n` on Windows and `\n` elsewhere.
+    Native,
+}
+
+impl NewlineStyle {
+    /// Gets the line ending to use for the formatted `input`.
+    pub fn resolve(self, input: &str) -> &'static str {
+        match self {
+            Self::Auto => match input.find('\n') {
+                Some(i) if input[..i].ends_with('\r') => "\r\n",
+                _ => "\n",
+            },
+            Self::Lf => "\n",
+            Self::Crlf => "\r\n",
+            Self::Native if cfg!(windows) => "\r\n",
+            Self::Native => "\n",
+        }
+    }
+}
+
+impl FromStr for NewlineStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "lf" => Ok(Self::Lf),
+            "crlf" => Ok(Self::Crlf),
+            "native" => Ok(Self::Native),
+            _ => Err(format!(
+                "invalid newline style: {s}, expected `auto`, `lf`, `crlf` or `native`"
+            )),
+        }
+    }
+}
+
+/// When to add trailing commas to lists.
+///
+/// Trailing commas that change the meaning, such as in one-element arrays `(1,)`, are always
+/// kept.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum TrailingComma {
+    /// Only add trailing commas when the list is broken into multiple lines.
+    #[default]
+    Vertical,
+    /// Always add trailing commas.
+    Always,
+    /// Never add trailing commas.
+    Never,
+    /// Keep trailing commas as in the source.
+    Preserve,
+}
+
+impl TrailingComma {
+    /// Returns whether to add an optional trailing comma when a list is flat and when it is
+    /// broken, given whether there is one in the source.
+    pub(crate) fn resolve(self, has_trailing_comma: bool) -> (bool, bool) {
+        match self {
+            Self::Vertical => (false, true),
+            Self::Always => (true, true),
+            Self::Never => (false, false),
+            Self::Preserve => (has_trailing_comma, has_trailing_comma),
+        }
+    }
+}
+
+impl FromStr for TrailingComma {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "vertical" => Ok(Self::Vertical),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            "preserve" => Ok(Self::Preserve),
+            _ => Err(format!(
+                "invalid trailing comma: {s}, expected `vertical`, `always`, `never` or `preserve`"
+            )),
+        }
+    }
+}
+
+/// The markers of enum items in markup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum EnumMarker {
+    /// Keep markers as in the source.
+    #[default]
+    Preserve,
+    /// Use auto-numbered `+` markers. Items keep their explicit numbers if they do not follow
+    /// the previous item, as in a sequence that does not start from 1.
+    Plus,
+    /// Use explicit numbers `1.`, `2.`, and so on, as Typst numbers the items.
+    Numbered,
 }
 
+impl FromStr for EnumMarker {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "preserve" => Ok(Self::Preserve),
+            "plus" => Ok(Self::Plus),
+            "numbered" => Ok(Self::Numbered),
+            _ => Err(format!(
+                "invalid enum marker: {s}, expected `preserve`, `plus` or `numbered`"
+            )),
+        }
+    }
+}
+
+/// How text in markup is wrapped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum WrapText {
+    /// Keep line breaks as in the source.
+    #[default]
+    Off,
+    /// Wrap text to fit within `max_width`.
+    Fill,
+    /// Put each sentence on its own line, joining the lines within a sentence.
+    Sentence,
+    /// Join the lines of each paragraph into a single line.
+    Unwrap,
+}
+
+impl WrapText {
+    /// Whether line breaks in markup may be changed.
+    pub fn is_enabled(self) -> bool {
+        self != Self::Off
+    }
+}
+
+impl From<bool> for WrapText {
+    fn from(value: bool) -> Self {
+        if value {
+            Self::Fill
+        } else {
+            Self::Off
+        }
+    }
+}
+
+impl FromStr for WrapText {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" | "false" => Ok(Self::Off),
+            "fill" | "true" => Ok(Self::Fill),
+            "sentence" => Ok(Self::Sentence),
+            "unwrap" => Ok(Self::Unwrap),
+            _ => Err(format!(
+                "invalid text wrapping: {s}, expected `off`, `fill`, `sentence` or `unwrap`"
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for WrapText {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Bool(bool),
+            Str(String),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Bool(value) => Ok(value.into()),
+            Repr::Str(s) => s.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+/// The default of `sentence_abbreviations`.
+const DEFAULT_SENTENCE_ABBREVIATIONS: &[&str] = &[
+    "e.g.", "i.e.", "etc.", "cf.", "vs.", "al.", "ca.", "approx.", "resp.", "Fig.", "Figs.", "Eq.",
+    "Eqs.", "Sec.", "Ch.", "No.", "Vol.", "pp.", "Dr.", "Mr.", "Mrs.", "Ms.", "Prof.", "St.",
+];
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             tab_spaces: 2,
+            indent_style: IndentStyle::Space,
+            newline_style: NewlineStyle::Auto,
             max_width: 80,
+            trailing_comma: TrailingComma::Vertical,
+            enum_marker: EnumMarker::Preserve,
             blank_lines_upper_bound: 2,
+            blank_lines_before_heading: None,
+            blank_lines_after_heading: None,
+            blank_lines_around_block: None,
             reorder_import_items: true,
+            reorder_import_statements: false,
+            remove_unused_imports: false,
             collapse_markup_spaces: false,
-            wrap_text: false,
+            wrap_text: WrapText::Off,
+            sentence_abbreviations: DEFAULT_SENTENCE_ABBREVIATIONS
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            tolerate_errors: false,
         }
     }
 }
@@ -45,13 +316,58 @@ impl Config {
         self
     }
 
+    pub fn with_indent_style(mut self, indent_style: IndentStyle) -> Self {
+        self.indent_style = indent_style;
+        self
+    }
+
+    pub fn with_newline_style(mut self, newline_style: NewlineStyle) -> Self {
+        self.newline_style = newline_style;
+        self
+    }
+
     pub fn chain_width(&self) -> usize {
         const CHAIN_WIDTH_RATIO: f32 = 0.6;
         (self.max_width as f32 * CHAIN_WIDTH_RATIO) as usize
     }
 
-    pub fn with_wrap_text(mut self, wrap_text: bool) -> Self {
-        self.wrap_text = wrap_text;
+    pub fn with_wrap_text(mut self, wrap_text: impl Into<WrapText>) -> Self {
+        self.wrap_text = wrap_text.into();
         self
     }
+
+    /// Sets an option that can vary within a file by its name, such as in a `@typstyle`
+    /// directive. Options that apply to the whole file, like `newline_style`, can not be set.
+    pub fn set_option(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let parse_usize = || {
+            value
+                .parse::<usize>()
+                .map_err(|_| format!("invalid value `{value}` for `{key}`, expected a number"))
+        };
+        let parse_bool = || {
+            value.parse::<bool>().map_err(|_| {
+                format!("invalid value `{value}` for `{key}`, expected `true` or `false`")
+            })
+        };
+        let parse_optional = || match value {
+            "none" => Ok(None),
+            _ => parse_usize().map(Some),
+        };
+        match key {
+            "max_width" => self.max_width = parse_usize()?,
+            "tab_spaces" => self.tab_spaces = parse_usize()?,
+            "indent_style" => self.indent_style = value.parse()?,
+            "trailing_comma" => self.trailing_comma = value.parse()?,
+            "enum_marker" => self.enum_marker = value.parse()?,
+            "blank_lines_upper_bound" => self.blank_lines_upper_bound = parse_usize()?,
+            "blank_lines_before_heading" => self.blank_lines_before_heading = parse_optional()?,
+            "blank_lines_after_heading" => self.blank_lines_after_heading = parse_optional()?,
+            "blank_lines_around_block" => self.blank_lines_around_block = parse_optional()?,
+            "collapse_markup_spaces" => self.collapse_markup_spaces = parse_bool()?,
+            "reorder_import_items" => self.reorder_import_items = parse_bool()?,
+            "wrap_text" => self.wrap_text = value.parse()?,
+            _ => return Err(format!("unknown option `{key}`")),
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,120 @@
+use std::ops::Range;
+
+use similar::{DiffOp, TextDiff};
+
+/// An edit that replaces a byte range of the source text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    /// The UTF-8 byte range in the source text to replace.
+    pub range: Range<usize>,
+    /// The text to replace the range with.
+    pub replacement: String,
+}
+
+impl TextEdit {
+    /// Computes the edits that turn `original` into `modified`.
+    ///
+    /// Changed lines are found first, and then compared by characters, so that each edit only
+    /// covers the characters that differ. The edits are sorted and do not overlap.
+    pub fn diff(original: &str, modified: &str) -> Vec<TextEdit> {
+        let mut edits = Vec::new();
+        let lines = TextDiff::from_lines(original, modified);
+        let old_offsets = offsets(lines.old_slices());
+        let new_offsets = offsets(lines.new_slices());
+        for group in lines.grouped_ops(0) {
+            let (old, new) = group_ranges(&group, &old_offsets, &new_offsets);
+            let (old_text, new_text) = (&original[old.clone()], &modified[new.clone()]);
+
+            let chars = TextDiff::from_chars(old_text, new_text);
+            let old_char_offsets = offsets(chars.old_slices());
+            let new_char_offsets = offsets(chars.new_slices());
+            for group in chars.grouped_ops(0) {
+                let (old_range, new_range) =
+                    group_ranges(&group, &old_char_offsets, &new_char_offsets);
+                edits.push(TextEdit {
+                    range: old.start + old_range.start..old.start + old_range.end,
+                    replacement: new_text[new_range].to_string(),
+                });
+            }
+        }
+        edits
+    }
+}
+
+/// Gets the byte offsets of the boundaries of the slices, including the end.
+fn offsets(slices: &[&str]) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(slices.iter().scan(0, |offset, slice| {
+            *offset += slice.len();
+            Some(*offset)
+        }))
+        .collect()
+}
+
+/// Gets the byte ranges in the old and new texts covered by a group of diff operations.
+fn group_ranges(
+    group: &[DiffOp],
+    old_offsets: &[usize],
+    new_offsets: &[usize],
+) -> (Range<usize>, Range<usize>) {
+    let (first, last) = (&group[0], &group[group.len() - 1]);
+    (
+        old_offsets[first.old_range().start]..old_offsets[last.old_range().end],
+        new_offsets[first.new_range().start]..new_offsets[last.new_range().end],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(original: &str, edits: &[TextEdit]) -> String {
+        let mut res = original.to_string();
+        for edit in edits.iter().rev() {
+            res.replace_range(edit.range.clone(), &edit.replacement);
+        }
+        res
+    }
+
+    #[test]
+    fn test_diff() {
+        let edits = TextEdit::diff("#let  x  = (1+2)", "#let x = (1 + 2)\n");
+        assert_eq!(
+            edits,
+            [
+                TextEdit {
+                    range: 5..6,
+                    replacement: "".to_string()
+                },
+                TextEdit {
+                    range: 7..8,
+                    replacement: "".to_string()
+                },
+                TextEdit {
+                    range: 13..13,
+                    replacement: " ".to_string()
+                },
+                TextEdit {
+                    range: 14..14,
+                    replacement: " ".to_string()
+                },
+                TextEdit {
+                    range: 16..16,
+                    replacement: "\n".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_multiline() {
+        let original = "= 标题\n\n#let a  =  1\n\n文本\n\n#f( x )\n";
+        let modified = "= 标题\n\n#let a = 1\n\n文本\n\n#f(x)\n";
+        let edits = TextEdit::diff(original, modified);
+        assert_eq!(apply(original, &edits), modified);
+        assert!(edits
+            .iter()
+            .all(|edit| edit.range.len() <= 2 && edit.replacement.len() <= 1));
+        assert!(TextEdit::diff(original, original).is_empty());
+    }
+}
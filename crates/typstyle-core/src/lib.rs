@@ -5,22 +5,83 @@ pub mod partial;
 pub mod pretty;
 
 mod config;
+mod edit;
+mod render;
 mod utils;
 
-pub use attr::AttrStore;
-pub use config::Config;
+use std::{fmt, ops::Range};
+
+pub use attr::{AttrStore, DirectiveDiagnostic};
+pub use config::{Config, EnumMarker, IndentStyle, NewlineStyle, TrailingComma, WrapText};
+use ecow::EcoString;
+pub use edit::TextEdit;
 use pretty::{prelude::*, PrettyPrinter};
 use thiserror::Error;
-use typst_syntax::Source;
+use typst_syntax::{Source, SyntaxNode};
 
 #[derive(Error, Debug)]
 pub enum Error {
+    /// The source has syntax errors, which are given in source order. They may be empty if
+    /// the part to format could not be found.
     #[error("The document has syntax errors")]
-    SyntaxError,
+    SyntaxError(Vec<SyntaxDiagnostic>),
     #[error("An error occurred while rendering the document")]
     RenderError,
 }
 
+impl Error {
+    /// Creates a [`Error::SyntaxError`] with the errors in `node`, which is part of `source`.
+    pub(crate) fn syntax(source: &Source, node: &SyntaxNode) -> Self {
+        Self::SyntaxError(SyntaxDiagnostic::collect(source, node))
+    }
+}
+
+/// A syntax error reported by the parser, with its location in the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxDiagnostic {
+    /// The byte range of the erroneous node.
+    pub range: Range<usize>,
+    /// The 1-based line where the error starts.
+    pub line: usize,
+    /// The 1-based column, in characters, where the error starts.
+    pub column: usize,
+    /// The error message.
+    pub message: EcoString,
+    /// Hints on how the error could be fixed.
+    pub hints: Vec<EcoString>,
+}
+
+impl SyntaxDiagnostic {
+    /// Collects the syntax errors in `node`, which is part of `source`.
+    fn collect(source: &Source, node: &SyntaxNode) -> Vec<Self> {
+        node.errors()
+            .into_iter()
+            .filter_map(|error| {
+                let range = source.range(error.span)?;
+                let line = source.byte_to_line(range.start)?;
+                let column = source.byte_to_column(range.start)?;
+                Some(Self {
+                    range,
+                    line: line + 1,
+                    column: column + 1,
+                    message: error.message,
+                    hints: error.hints.into_iter().collect(),
+                })
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for SyntaxDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)?;
+        for hint in &self.hints {
+            write!(f, "\n  hint: {hint}")?;
+        }
+        Ok(())
+    }
+}
+
 /// Main struct for Typst formatting.
 #[derive(Debug, Clone, Default)]
 pub struct Typstyle {
@@ -53,11 +114,25 @@ pub struct Formatter<'a> {
 
 impl<'a> Formatter<'a> {
     fn new(config: Config, source: Source) -> Self {
-        let attr_store = AttrStore::new(source.root());
+        let mut attr_store = if config.tolerate_errors {
+            AttrStore::new_tolerant(source.root())
+        } else {
+            AttrStore::new(source.root())
+        };
+        if config.remove_unused_imports {
+            attr_store.compute_unused_imports(source.root());
+        }
         let printer = PrettyPrinter::new(config, attr_store);
         Self { source, printer }
     }
 
+    /// Gets the problems with `@typstyle` directives in the source, such as directives that
+    /// have no effect. They are empty if the source has syntax errors, unless formatting is
+    /// error-tolerant.
+    pub fn diagnostics(&self) -> &[DirectiveDiagnostic] {
+        self.printer.attr_store().diagnostics()
+    }
+
     /// Renders the document's pretty IR.
     pub fn render_ir(&'a self) -> Result<String, Error> {
         let doc = self.build_doc()?;
@@ -66,18 +141,35 @@ impl<'a> Formatter<'a> {
 
     /// Renders the formatted document to a string.
     pub fn render(&'a self) -> Result<String, Error> {
+        if attr::is_file_format_disabled(self.source.root()) {
+            return Ok(self.source.text().to_string());
+        }
         let doc = self.build_doc()?;
-        let mut buf = String::new();
-        doc.render_fmt(self.printer.config().max_width, &mut buf)
-            .map_err(|_| Error::RenderError)?;
-        let result = utils::strip_trailing_whitespace(&buf);
+        let buf =
+            render::render_doc(&doc, &self.printer.config()).map_err(|_| Error::RenderError)?;
+        let newline = self
+            .printer
+            .config()
+            .newline_style
+            .resolve(self.source.text());
+        let result = utils::strip_trailing_whitespace(&buf, newline);
         Ok(result)
     }
 
+    /// Renders the formatted document as edits against the original source.
+    ///
+    /// Unlike [`Formatter::render`], unchanged parts of the source are not replaced, so that
+    /// editors can keep cursors and markers there.
+    pub fn render_edits(&'a self) -> Result<Vec<TextEdit>, Error> {
+        let res = self.render()?;
+        Ok(TextEdit::diff(self.source.text(), &res))
+    }
+
     fn build_doc(&'a self) -> Result<ArenaDoc<'a>, Error> {
         let root = self.source.root();
-        if root.erroneous() {
-            return Err(Error::SyntaxError);
+        // Erroneous parts are kept verbatim in error-tolerant formatting.
+        if root.erroneous() && !self.printer.config().tolerate_errors {
+            return Err(Error::syntax(&self.source, root));
         }
         let markup = root.cast().unwrap();
         let doc = self.printer.convert_markup(Default::default(), markup);
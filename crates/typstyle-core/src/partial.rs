@@ -1,4 +1,4 @@
-use std::ops::Range;
+use std::{cmp::Reverse, ops::Range};
 
 use typst_syntax::{
     ast::{Expr, Markup, Pattern},
@@ -6,8 +6,9 @@ use typst_syntax::{
 };
 
 use crate::{
+    attr,
     pretty::{Context, Mode},
-    utils, AttrStore, Error, PrettyPrinter, Typstyle,
+    render, utils, AttrStore, Error, PrettyPrinter, TextEdit, Typstyle,
 };
 
 impl Typstyle {
@@ -20,13 +21,77 @@ impl Typstyle {
         // Trim the give range to ensure no space aside.
         let range = utils::trim_range(source.text(), utf8_range);
 
-        let Some((node, mode)) =
-            get_node_cover_range(source, range.clone()).filter(|(node, _)| !node.erroneous())
-        else {
-            return Err(Error::SyntaxError);
+        let Some((node, mode)) = get_node_cover_range(source, range.clone()) else {
+            return Err(Error::syntax(source, source.root()));
         };
+        let res = self.format_node(source, &node, mode, range.start)?;
+        Ok((node.range(), res))
+    }
+
+    /// Format the nodes with minimal spans that covering the given ranges.
+    ///
+    /// When the covering node of a range contains that of another, only the outer one is
+    /// formatted. The returned edits are sorted and do not overlap, so they can be applied
+    /// together.
+    pub fn format_source_ranges(
+        &self,
+        source: &Source,
+        utf8_ranges: impl IntoIterator<Item = Range<usize>>,
+    ) -> Result<Vec<TextEdit>, Error> {
+        let mut nodes = Vec::new();
+        for range in utf8_ranges {
+            let range = utils::trim_range(source.text(), range);
+            let Some((node, mode)) = get_node_cover_range(source, range.clone()) else {
+                return Err(Error::syntax(source, source.root()));
+            };
+            nodes.push((node, mode, range.start));
+        }
+
+        // Nodes in a tree either nest or are disjoint, so we only need to drop nested ones.
+        nodes.sort_by_key(|(node, _, _)| (node.range().start, Reverse(node.range().end)));
+        nodes.dedup_by(|(inner, _, _), (outer, _, _)| inner.range().end <= outer.range().end);
 
-        let attrs = AttrStore::new(node.get()); // Here we only compute the attributes of that subtree.
+        nodes
+            .into_iter()
+            .map(|(node, mode, start)| {
+                let replacement = self.format_node(source, &node, mode, start)?;
+                Ok(TextEdit {
+                    range: node.range(),
+                    replacement,
+                })
+            })
+            .collect()
+    }
+
+    /// Format a Markup/Expr/Pattern node, with the indent of the line containing `start`.
+    fn format_node(
+        &self,
+        source: &Source,
+        node: &LinkedNode,
+        mode: Mode,
+        start: usize,
+    ) -> Result<String, Error> {
+        if attr::is_file_format_disabled(source.root()) {
+            return Ok(node.get().clone().into_text().to_string());
+        }
+        if node.erroneous() && !self.config.tolerate_errors {
+            return Err(Error::syntax(source, node.get()));
+        }
+
+        // Here we only compute the attributes of that subtree.
+        let mut attrs = if self.config.tolerate_errors {
+            AttrStore::new_tolerant(node.get())
+        } else {
+            AttrStore::new(node.get())
+        };
+        if attrs.is_format_disabled(node.get()) {
+            // The node is erroneous as a whole.
+            return Ok(node.get().clone().into_text().to_string());
+        }
+        if self.config.remove_unused_imports {
+            // References may be anywhere in the source.
+            attrs.compute_unused_imports(source.root());
+        }
         let printer = PrettyPrinter::new(self.config.clone(), attrs);
         let ctx = Context::default().with_mode(mode);
         let doc = if let Some(markup) = node.cast() {
@@ -36,15 +101,15 @@ impl Typstyle {
         } else if let Some(pattern) = node.cast() {
             printer.convert_pattern(ctx, pattern)
         } else {
-            return Err(Error::SyntaxError);
+            return Err(Error::SyntaxError(vec![]));
         };
         // Infer indent from context.
-        let indent = utils::count_spaces_after_last_newline(source.text(), range.start);
-        let res = doc
-            .nest(indent as isize)
-            .pretty(self.config.max_width)
-            .to_string();
-        Ok((node.range(), res))
+        let indent =
+            utils::count_indent_after_last_newline(source.text(), start, self.config.tab_spaces);
+        let res = render::render_doc(&doc.nest(indent as isize), &self.config)
+            .map_err(|_| Error::RenderError)?;
+        let newline = self.config.newline_style.resolve(source.text());
+        Ok(utils::replace_newlines(res, newline))
     }
 }
 
@@ -92,7 +92,7 @@ impl<'a> PrettyPrinter<'a> {
                         .unwrap_or_default()
                 })
                 .sum::<usize>();
-        if estimated_len >= self.config.chain_width() {
+        if estimated_len >= self.config().chain_width() {
             return None;
         }
 
@@ -1,12 +1,13 @@
-use typst_syntax::{ast::*, SyntaxKind};
+use typst_syntax::{ast::*, SyntaxKind, SyntaxNode};
 
 use super::{
     layout::list::{ListStyle, ListStylist},
     prelude::*,
     style::FoldStyle,
-    util::{has_comment_children, is_only_one_and},
+    util::{has_comment_children, is_comment_node, is_only_one_and},
     Context, Mode, PrettyPrinter,
 };
+use crate::ext::StrExt;
 
 impl<'a> PrettyPrinter<'a> {
     pub(super) fn convert_code_block(
@@ -31,6 +32,22 @@ impl<'a> PrettyPrinter<'a> {
                 nodes.push(child);
             }
         }
+        // Regions where formatting is disabled are kept verbatim as single items.
+        let mut regions = vec![];
+        let mut i = 0;
+        while i < nodes.len() {
+            if let Some(len) = self.attr_store.disabled_region_len(nodes[i]) {
+                regions.push((
+                    nodes[i].span(),
+                    self.convert_verbatim_nodes(&nodes[i..=i + len]),
+                ));
+                nodes.drain(i + 1..=i + len);
+            }
+            i += 1;
+        }
+        if self.config().remove_unused_imports {
+            self.remove_unused_import_exprs(&mut nodes);
+        }
 
         let can_fold = code_block.body().exprs().count() <= 1
             && !has_comment_children(code_block.to_untyped());
@@ -41,9 +58,16 @@ impl<'a> PrettyPrinter<'a> {
             } else {
                 FoldStyle::Never
             })
-            .keep_linebreak(self.config.blank_lines_upper_bound)
-            .process_iterable(ctx, nodes.into_iter(), |ctx, expr| {
-                self.convert_expr(ctx, expr)
+            .keep_linebreak(self.config().blank_lines_upper_bound)
+            .process_iterable_impl(ctx, nodes.into_iter(), |ctx, node| {
+                match regions.iter().find(|(span, _)| *span == node.span()) {
+                    Some((_, region)) => Some(region.clone()),
+                    // Only in error-tolerant formatting.
+                    None if node.kind() == SyntaxKind::Error => {
+                        Some(self.convert_verbatim_untyped(node))
+                    }
+                    None => node.cast().map(|expr| self.convert_expr(ctx, expr)),
+                }
             })
             .print_doc(ListStyle {
                 separator: "",
@@ -53,6 +77,34 @@ impl<'a> PrettyPrinter<'a> {
             })
     }
 
+    /// Removes imports whose items are all unused, along with the rest of their lines.
+    /// Imports followed by comments on the same line are kept.
+    fn remove_unused_import_exprs(&self, nodes: &mut Vec<&'a SyntaxNode>) {
+        let mut i = 0;
+        while i < nodes.len() {
+            if !self.attr_store.is_unused_import(nodes[i]) {
+                i += 1;
+                continue;
+            }
+            let mut end = i + 1;
+            while nodes.get(end).is_some_and(|node| {
+                node.kind() == SyntaxKind::Semicolon
+                    || node.kind() == SyntaxKind::Space && !node.text().has_linebreak()
+            }) {
+                end += 1;
+            }
+            match nodes.get(end) {
+                Some(node) if is_comment_node(node) => i = end,
+                Some(node) if node.kind() == SyntaxKind::Space => {
+                    nodes.drain(i..=end);
+                }
+                _ => {
+                    nodes.drain(i..end);
+                }
+            }
+        }
+    }
+
     pub(super) fn convert_parenthesized_impl(
         &'a self,
         ctx: Context,
@@ -111,6 +163,11 @@ impl<'a> PrettyPrinter<'a> {
             .print_doc(ListStyle {
                 add_trailing_sep_single: is_explicit,
                 add_trailing_sep_always: ends_with_comma,
+                trailing_sep: if is_explicit {
+                    self.config().trailing_comma
+                } else {
+                    Default::default()
+                },
                 delim: if is_explicit { ("(", ")") } else { ("", "") },
                 tight_delim: !is_explicit,
                 no_indent: !is_explicit,
@@ -130,6 +187,7 @@ impl<'a> PrettyPrinter<'a> {
             })
             .print_doc(ListStyle {
                 delim: (if all_spread { "(:" } else { "(" }, ")"),
+                trailing_sep: self.config().trailing_comma,
                 ..Default::default()
             })
     }
@@ -153,6 +211,7 @@ impl<'a> PrettyPrinter<'a> {
             .always_fold_if(|| only_one_pattern)
             .print_doc(ListStyle {
                 add_trailing_sep_single: only_one_pattern,
+                trailing_sep: self.config().trailing_comma,
                 ..Default::default()
             })
     }
@@ -182,6 +241,7 @@ impl<'a> PrettyPrinter<'a> {
             .always_fold_if(|| is_single_simple)
             .print_doc(ListStyle {
                 omit_delim_single: is_single_simple,
+                trailing_sep: self.config().trailing_comma,
                 ..Default::default()
             })
     }
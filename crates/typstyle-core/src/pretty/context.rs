@@ -3,6 +3,8 @@ pub struct Context {
     pub mode: Mode,
     pub break_suppressed: bool,
     pub align_mode: AlignMode,
+    /// Whether tables are formatted as plain function calls, without aligning columns.
+    pub table_skipped: bool,
 }
 
 impl Context {
@@ -29,6 +31,13 @@ impl Context {
         }
     }
 
+    pub fn skip_table(self) -> Self {
+        Self {
+            table_skipped: true,
+            ..self
+        }
+    }
+
     pub fn aligned(self, mode: AlignMode) -> Self {
         Self {
             align_mode: match (self.align_mode, mode) {
@@ -63,7 +63,7 @@ impl<'a> PrettyPrinter<'a> {
 
         let mut doc = self.arena.nil();
         let has_parenthesized_args = has_parenthesized_args(args);
-        if table::is_table(func_call) {
+        if table::is_table(func_call) && !ctx.table_skipped {
             if let Some(table) = self.try_convert_table(ctx, func_call) {
                 doc += table;
             } else if has_parenthesized_args {
@@ -107,13 +107,14 @@ impl<'a> PrettyPrinter<'a> {
         };
 
         ListStylist::new(self)
-            .keep_linebreak(self.config.blank_lines_upper_bound)
+            .keep_linebreak(self.config().blank_lines_upper_bound)
             .with_fold_style(fold_style)
             .process_iterable_impl(ctx, get_children(), |ctx, child| {
                 // We should ignore additional args here.
                 child.cast().map(|arg| self.convert_arg(ctx, arg))
             })
             .print_doc(ListStyle {
+                trailing_sep: self.config().trailing_comma,
                 ..Default::default()
             })
     }
@@ -126,7 +127,7 @@ impl<'a> PrettyPrinter<'a> {
                 self.convert_arg(ctx, child)
             })
             .print_doc();
-        inner.nest(self.config.tab_spaces as isize).parens()
+        inner.nest(self.config().tab_spaces as isize).parens()
     }
 
     fn convert_args_in_math(&'a self, ctx: Context, args: Args<'a>) -> ArenaDoc<'a> {
@@ -198,7 +199,7 @@ impl<'a> PrettyPrinter<'a> {
             }
         });
         if self.attr_store.is_multiline(args.to_untyped()) {
-            ((self.arena.line_() + inner).nest(self.config.tab_spaces as isize)
+            ((self.arena.line_() + inner).nest(self.config().tab_spaces as isize)
                 + self.arena.line_())
             .group()
             .parens()
@@ -1,4 +1,6 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+
+use ecow::EcoString;
 
 use typst_syntax::{ast::*, SyntaxKind, SyntaxNode};
 
@@ -14,6 +16,16 @@ use super::{
 
 impl<'a> PrettyPrinter<'a> {
     pub(super) fn convert_import(&'a self, ctx: Context, import: ModuleImport<'a>) -> ArenaDoc<'a> {
+        self.convert_import_merged(ctx, import, &[])
+    }
+
+    /// Converts an import, with the items of duplicate imports of the same module merged into it.
+    pub(super) fn convert_import_merged(
+        &'a self,
+        ctx: Context,
+        import: ModuleImport<'a>,
+        merged_items: &[&'a SyntaxNode],
+    ) -> ArenaDoc<'a> {
         // ImportItems are optional and may be wrapped in parentheses.
         let nodes = import.to_untyped().children().as_slice();
 
@@ -71,6 +83,19 @@ impl<'a> PrettyPrinter<'a> {
         if import_items_nodes.is_empty() {
             return prefix_doc;
         }
+        let merge_pos = import_items_nodes
+            .iter()
+            .rposition(|node| node.kind() == SyntaxKind::RightParen)
+            .unwrap_or(import_items_nodes.len());
+        import_items_nodes.splice(merge_pos..merge_pos, merged_items.iter().copied());
+        // Unused items are kept if all of them are, as an import can not be left without items.
+        if self.config().remove_unused_imports
+            && import_items_nodes.iter().any(|node| {
+                node.is::<ImportItemPath>() || node.is::<RenamedImportItem>()
+            } && !self.attr_store.is_unused_import(node))
+        {
+            import_items_nodes.retain(|node| !self.attr_store.is_unused_import(node));
+        }
 
         let import_items_doc = self.convert_import_items(ctx, import_items_nodes);
         prefix_doc + self.arena.space() + import_items_doc
@@ -83,7 +108,7 @@ impl<'a> PrettyPrinter<'a> {
     ) -> ArenaDoc<'a> {
         // Sort import items if the configuration allows it.
         // The sorting is only applied if all nodes are not comments and if there are no duplicate names.
-        if self.config.reorder_import_items
+        if self.config().reorder_import_items
             && import_items_nodes.iter().all(|node| !is_comment_node(node))
             && check_import_name_duplication(&import_items_nodes)
         {
@@ -146,6 +171,98 @@ impl<'a> PrettyPrinter<'a> {
     }
 }
 
+impl PrettyPrinter<'_> {
+    /// Checks whether reordering the imports keeps their meaning, that is, no name is bound by
+    /// imports of different modules, and there are no wildcard imports.
+    pub(super) fn can_reorder_imports<'b>(
+        &self,
+        imports: impl Iterator<Item = ModuleImport<'b>>,
+    ) -> bool {
+        let mut bound = HashMap::new();
+        for import in imports {
+            let Some(source) = import_source(import) else {
+                return false;
+            };
+            let mut names = vec![];
+            if let Some(new_name) = import.new_name() {
+                names.push(new_name.get().clone());
+            }
+            match import.imports() {
+                Some(Imports::Wildcard) => return false,
+                Some(Imports::Items(items)) => {
+                    names.extend(items.iter().map(|item| item.bound_name().get().clone()))
+                }
+                None if import.new_name().is_none() => match import.bare_name() {
+                    Ok(name) => names.push(name),
+                    Err(_) => return false,
+                },
+                None => {}
+            }
+            for name in names {
+                if bound
+                    .insert(name, source.clone())
+                    .is_some_and(|prev| prev != source)
+                {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Checks whether the items of `import` can be merged into `prev`.
+    pub(super) fn can_merge_imports(
+        &self,
+        prev: ModuleImport<'_>,
+        import: ModuleImport<'_>,
+    ) -> bool {
+        let can_merge = |import: ModuleImport<'_>| {
+            import.new_name().is_none()
+                && matches!(import.imports(), Some(Imports::Items(_)))
+                && !import
+                    .to_untyped()
+                    .children()
+                    .chain(import_items_children(import))
+                    .any(is_comment_node)
+        };
+        import_source(prev).is_some_and(|source| Some(source) == import_source(import))
+            && can_merge(prev)
+            && can_merge(import)
+    }
+}
+
+/// Gets the path of a module imported from a string literal.
+fn import_source(import: ModuleImport<'_>) -> Option<EcoString> {
+    match import.source() {
+        Expr::Str(source) => Some(source.get()),
+        _ => None,
+    }
+}
+
+/// Gets the key to sort imports by, putting packages before local files.
+pub(super) fn import_sort_key(import: ModuleImport<'_>) -> (bool, EcoString) {
+    let source = import_source(import).unwrap_or_default();
+    (!source.starts_with('@'), source)
+}
+
+/// Gets the nodes of the import items, excluding separators.
+pub(super) fn import_item_nodes(import: ModuleImport<'_>) -> impl Iterator<Item = &SyntaxNode> {
+    import_items_children(import).filter(|node| {
+        matches!(
+            node.kind(),
+            SyntaxKind::ImportItemPath | SyntaxKind::RenamedImportItem
+        )
+    })
+}
+
+fn import_items_children(import: ModuleImport<'_>) -> impl Iterator<Item = &SyntaxNode> {
+    import
+        .to_untyped()
+        .children()
+        .filter(|node| node.kind() == SyntaxKind::ImportItems)
+        .flat_map(|items| items.children())
+}
+
 /// Check for duplicate import names in the given import items nodes.
 /// Returns `true` if no duplicates are found, `false` otherwise.
 fn check_import_name_duplication(import_items_nodes: &[&SyntaxNode]) -> bool {
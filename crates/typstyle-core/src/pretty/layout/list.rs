@@ -1,6 +1,7 @@
 use typst_syntax::{ast::*, SyntaxKind, SyntaxNode};
 
 use crate::{
+    config::TrailingComma,
     ext::StrExt,
     pretty::{prelude::*, style::FoldStyle, Context, Mode, PrettyPrinter},
 };
@@ -15,6 +16,8 @@ pub struct ListStylist<'a> {
     real_item_count: usize,
     has_comment: bool,
     has_line_comment: bool,
+    /// Whether the last item is followed by a separator in the source.
+    has_trailing_sep: bool,
 
     fold_style: FoldStyle,
     disallow_front_comment: bool,
@@ -50,6 +53,8 @@ pub struct ListStyle {
     pub add_trailing_sep_single: bool,
     /// Whether a trailing separator is always needed.
     pub add_trailing_sep_always: bool,
+    /// When to add a trailing separator if it is not needed.
+    pub trailing_sep: TrailingComma,
     /// Whether can omit the delimiter if the list contains only one item.
     pub omit_delim_single: bool,
     /// Whether can omit the delimiter if the list is flat.
@@ -71,6 +76,7 @@ impl Default for ListStyle {
             add_delim_space: false,
             add_trailing_sep_single: false,
             add_trailing_sep_always: false,
+            trailing_sep: TrailingComma::Vertical,
             omit_delim_single: false,
             omit_delim_flat: false,
             omit_delim_empty: false,
@@ -92,6 +98,7 @@ impl<'a> ListStylist<'a> {
             real_item_count: 0,
             has_comment: false,
             has_line_comment: false,
+            has_trailing_sep: false,
 
             fold_style: FoldStyle::Fit,
             disallow_front_comment: false,
@@ -150,17 +157,6 @@ impl<'a> ListStylist<'a> {
         self.process_iterable_impl(ctx, list_node.children(), item_checker)
     }
 
-    pub fn process_iterable<T: AstNode<'a>>(
-        self,
-        ctx: Context,
-        iterable: impl Iterator<Item = &'a SyntaxNode>,
-        item_converter: impl Fn(Context, T) -> ArenaDoc<'a>,
-    ) -> Self {
-        self.process_iterable_impl(ctx, iterable, |ctx, node| {
-            node.cast().map(|node| item_converter(ctx, node))
-        })
-    }
-
     /// Process an iterable of nodes.
     pub fn process_iterable_impl(
         mut self,
@@ -192,6 +188,7 @@ impl<'a> ListStylist<'a> {
         let arena = &self.printer.arena;
 
         self.real_item_count += 1;
+        self.has_trailing_sep = false;
         let before = if self.disallow_front_comment {
             self.detach_comments();
             arena.nil()
@@ -236,6 +233,7 @@ impl<'a> ListStylist<'a> {
                     .push(self.printer.convert_comment(ctx, node));
             }
             SyntaxKind::Comma => {
+                self.has_trailing_sep = true;
                 self.try_attach_comments();
             }
             SyntaxKind::Space => {
@@ -316,7 +314,17 @@ impl<'a> ListStylist<'a> {
 
         let is_single = self.real_item_count == 1;
         let sep = arena.text(sty.separator);
-        let indent = self.printer.config.tab_spaces;
+        // Whether to add a trailing separator when the list is flat or broken.
+        let (trailing_sep_flat, trailing_sep_break) =
+            if sty.add_trailing_sep_always || is_single && sty.add_trailing_sep_single {
+                (true, true)
+            } else if is_single && sty.omit_delim_single || sty.omit_delim_flat {
+                // The delimiters may be omitted, so we can't add a separator after the last item.
+                (false, true)
+            } else {
+                sty.trailing_sep.resolve(self.has_trailing_sep)
+            };
+        let indent = self.printer.config().tab_spaces;
         let fold_style = if self.has_line_comment {
             FoldStyle::Never
         } else {
@@ -360,7 +368,12 @@ impl<'a> ListStylist<'a> {
                         Item::Comment(cmt) => inner += cmt + arena.hardline(),
                         Item::Commented { body, after } => {
                             seen_real_items += 1;
-                            inner += body + sep.clone() + after;
+                            let is_last_real = seen_real_items == self.real_item_count;
+                            inner += if is_last_real && !trailing_sep_break {
+                                body + after
+                            } else {
+                                body + sep.clone() + after
+                            };
                             if !sty.tight_delim || !is_last {
                                 inner += arena.hardline();
                             }
@@ -392,9 +405,7 @@ impl<'a> ListStylist<'a> {
                             inner += body + after;
                             if !is_last_real {
                                 inner += sep.clone() + arena.space();
-                            } else if sty.add_trailing_sep_always
-                                || is_single && sty.add_trailing_sep_single
-                            {
+                            } else if trailing_sep_flat {
                                 // trailing comma for one-size array
                                 inner += sep.clone();
                             }
@@ -431,13 +442,17 @@ impl<'a> ListStylist<'a> {
                 let last = docs.pop().unwrap();
                 let inner = if docs.is_empty() {
                     // only one item
-                    let last = if sty.add_trailing_sep_single {
+                    let compact = if trailing_sep_flat {
+                        last.clone() + sep.clone()
+                    } else {
+                        last.clone()
+                    };
+                    let loose = if trailing_sep_break {
                         last + sep.clone()
                     } else {
                         last
                     };
-                    let compact = last.clone();
-                    let loose = (arena.line_() + last + sep.clone()).nest(2) + arena.line_();
+                    let loose = (arena.line_() + loose).nest(2) + arena.line_();
                     compact.union(loose)
                 } else {
                     // NOTE: we can't pad here, since this can appear in inline chains.
@@ -446,13 +461,22 @@ impl<'a> ListStylist<'a> {
                         sep.clone() + arena.space(),
                     ) + sep.clone()
                         + arena.space()
-                        + last.clone();
+                        + last.clone()
+                        + if trailing_sep_flat {
+                            sep.clone()
+                        } else {
+                            arena.nil()
+                        };
                     let loose = (arena.line_()
                         + arena.intersperse(docs.clone(), sep.clone() + arena.line())
                         + sep.clone()
                         + arena.line()
                         + last
-                        + sep.clone()
+                        + if trailing_sep_break {
+                            sep.clone()
+                        } else {
+                            arena.nil()
+                        }
                         + arena.line_())
                     .nest(2);
                     compact.union(loose)
@@ -485,29 +509,25 @@ impl<'a> ListStylist<'a> {
                         Item::Commented { body, after } => {
                             seen_real_items += 1;
                             let is_last_real = seen_real_items == self.real_item_count;
+                            let sep_flat = !is_last_real || trailing_sep_flat;
+                            let sep_break = !is_last_real || trailing_sep_break;
                             let follow = if let Some(after) = after {
-                                let follow_break = sep.clone() + after.clone();
-                                let follow_flat = if !is_last_real
-                                    || sty.add_trailing_sep_always
-                                    || is_single && sty.add_trailing_sep_single
-                                {
-                                    after + sep.clone()
+                                let follow_break = if sep_break {
+                                    sep.clone() + after.clone()
                                 } else {
-                                    after
+                                    after.clone()
                                 };
+                                let follow_flat =
+                                    if sep_flat { after + sep.clone() } else { after };
                                 follow_break.flat_alt(follow_flat)
+                            } else if is_last_real && sty.tight_delim {
+                                arena.nil()
                             } else {
-                                let follow = if is_last_real && sty.tight_delim {
-                                    arena.nil()
-                                } else if !is_last_real
-                                    || sty.add_trailing_sep_always
-                                    || is_single && sty.add_trailing_sep_single
-                                {
-                                    sep.clone()
-                                } else {
-                                    sep.clone().flat_alt(arena.nil())
-                                };
-                                follow
+                                match (sep_flat, sep_break) {
+                                    (true, _) => sep.clone(),
+                                    (false, true) => sep.clone().flat_alt(arena.nil()),
+                                    (false, false) => arena.nil(),
+                                }
                             };
                             let ln = if !is_last_real {
                                 ln.clone()
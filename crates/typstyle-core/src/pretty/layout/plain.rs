@@ -46,7 +46,7 @@ impl<'a> PlainStylist<'a> {
         iterable: impl Iterator<Item = &'a SyntaxNode>,
         item_converter: impl Fn(Context, T) -> ArenaDoc<'a>,
     ) -> Self {
-        let nl = self.printer.config.blank_lines_upper_bound;
+        let nl = self.printer.config().blank_lines_upper_bound;
         for child in iterable {
             self.items.push(match child.kind() {
                 SyntaxKind::Comma => PlainItem::Comma,
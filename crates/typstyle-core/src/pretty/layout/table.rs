@@ -9,6 +9,8 @@ pub struct TableCollector<'a> {
     rows: Vec<Row<'a>>,
     /// A buffer for cell docs in the current row.
     current_row_cells: Vec<ArenaDoc<'a>>,
+    /// Whether to add a trailing comma when the table is flat and when it is broken.
+    trailing_sep: (bool, bool),
 }
 
 enum Row<'a> {
@@ -30,10 +32,16 @@ impl<'a> TableCollector<'a> {
             columns,
             rows: vec![],
             current_row_cells: Vec::with_capacity(columns.max(2)),
+            trailing_sep: (false, true),
             arena,
         }
     }
 
+    pub fn with_trailing_sep(mut self, flat: bool, broken: bool) -> Self {
+        self.trailing_sep = (flat, broken);
+        self
+    }
+
     pub fn push_cell(&mut self, doc: ArenaDoc<'a>) {
         self.current_row_cells.push(doc);
         if self.current_row_cells.len() == self.columns {
@@ -90,23 +98,40 @@ impl<'a> TableCollector<'a> {
         }
         let num_rows = self.rows.len();
         let only_one_row = num_rows == 1;
+        let last_item_row = self
+            .rows
+            .iter()
+            .rposition(|row| matches!(row, Row::Cells { .. } | Row::Block(_)));
+        let (trailing_sep_flat, trailing_sep_break) = self.trailing_sep;
+        let sep = |i: usize, is_cells: bool| {
+            let comma = self.arena.text(",");
+            if Some(i) != last_item_row {
+                comma
+            } else if only_one_row && is_cells {
+                match (trailing_sep_flat, trailing_sep_break) {
+                    (true, _) => comma,
+                    (false, true) => comma.flat_alt(self.arena.nil()),
+                    (false, false) => self.arena.nil(),
+                }
+            } else if trailing_sep_break {
+                comma
+            } else {
+                self.arena.nil()
+            }
+        };
         self.arena.intersperse(
             self.rows.into_iter().enumerate().map(|(i, row)| match row {
                 Row::Cells {
                     mut doc,
                     auto_break,
                 } => {
-                    doc += if only_one_row {
-                        self.arena.text(",").flat_alt(self.arena.nil())
-                    } else {
-                        self.arena.text(",")
-                    };
+                    doc += sep(i, true);
                     if i + 1 < num_rows && auto_break {
                         doc += self.arena.line_()
                     }
                     doc.group()
                 }
-                Row::Block(doc) => doc + self.arena.text(","),
+                Row::Block(doc) => doc + sep(i, false),
                 Row::Comment(doc) => doc,
                 Row::Linebreak => self.arena.nil(),
             }),
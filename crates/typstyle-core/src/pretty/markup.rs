@@ -2,9 +2,17 @@ use smallvec::SmallVec;
 use typst_syntax::{ast::*, SyntaxKind, SyntaxNode};
 
 use super::{
-    layout::flow::FlowItem, prelude::*, util::is_comment_node, Context, Mode, PrettyPrinter,
+    import::{import_item_nodes, import_sort_key},
+    layout::flow::FlowItem,
+    prelude::*,
+    util::{is_cjk, is_comment_node},
+    Context, Mode, PrettyPrinter,
+};
+use crate::{
+    attr::{AttrStore, EnumNumber},
+    config::{EnumMarker, WrapText},
+    ext::StrExt,
 };
-use crate::ext::StrExt;
 
 #[derive(Debug, PartialEq, Eq)]
 enum MarkupScope {
@@ -38,7 +46,7 @@ impl<'a> PrettyPrinter<'a> {
     ) -> ArenaDoc<'a> {
         let content = self
             .convert_markup_impl(ctx, content_block.body(), MarkupScope::ContentBlock)
-            .nest(self.config.tab_spaces as isize);
+            .nest(self.config().tab_spaces as isize);
         content.group().brackets()
     }
 
@@ -148,12 +156,13 @@ impl<'a> PrettyPrinter<'a> {
             }
             _ => FlowItem::none(),
         })
-        .nest(self.config.tab_spaces as isize)
+        .nest(self.config().tab_spaces as isize)
     }
 
     fn convert_list_item_like(&'a self, ctx: Context, item: &'a SyntaxNode) -> ArenaDoc<'a> {
         self.convert_flow_like(ctx, item, |ctx, child, _| match child.kind() {
-            SyntaxKind::ListMarker | SyntaxKind::EnumMarker | SyntaxKind::TermMarker => {
+            SyntaxKind::EnumMarker => FlowItem::spaced(self.convert_enum_marker(item, child)),
+            SyntaxKind::ListMarker | SyntaxKind::TermMarker => {
                 FlowItem::spaced(self.arena.text(child.text().as_str()))
             }
             SyntaxKind::Space if child.text().has_linebreak() => {
@@ -174,7 +183,19 @@ impl<'a> PrettyPrinter<'a> {
             }
             _ => FlowItem::none(),
         })
-        .nest(self.config.tab_spaces as isize)
+        .nest(self.config().tab_spaces as isize)
+    }
+
+    fn convert_enum_marker(&'a self, item: &'a SyntaxNode, marker: &'a SyntaxNode) -> ArenaDoc<'a> {
+        let Some(EnumNumber { number, is_implied }) = self.attr_store.enum_number(item) else {
+            return self.arena.text(marker.text().as_str());
+        };
+        match self.config().enum_marker {
+            EnumMarker::Preserve => self.arena.text(marker.text().as_str()),
+            // Numbers that do not follow the previous item can not be expressed with `+`.
+            EnumMarker::Plus if is_implied => self.arena.text("+"),
+            EnumMarker::Plus | EnumMarker::Numbered => self.arena.text(format!("{number}.")),
+        }
     }
 
     fn convert_markup_impl(
@@ -193,8 +214,17 @@ impl<'a> PrettyPrinter<'a> {
             }
         }
 
-        let repr = collect_markup_repr(markup);
-        let body = if self.config.wrap_text && scope != MarkupScope::InlineItem {
+        let mut repr = collect_markup_repr(markup, &self.attr_store);
+        if self.config().remove_unused_imports && scope != MarkupScope::InlineItem {
+            self.remove_unused_import_lines(&mut repr);
+        }
+        if scope == MarkupScope::Document && self.config().reorder_import_statements {
+            self.reorder_import_lines(&mut repr);
+        }
+        if scope != MarkupScope::InlineItem {
+            self.normalize_blank_lines(&mut repr);
+        }
+        let body = if self.config().wrap_text.is_enabled() && scope != MarkupScope::InlineItem {
             self.convert_markup_body_reflow(ctx, &repr)
         } else {
             self.convert_markup_body(ctx, &repr)
@@ -202,8 +232,8 @@ impl<'a> PrettyPrinter<'a> {
 
         // Add line or space (if any) to both sides.
         // Only turn space into, not the other way around.
-        let prefer_tight =
-            !self.config.wrap_text && !self.attr_store.is_multiline(markup.to_untyped());
+        let prefer_tight = !self.config().wrap_text.is_enabled()
+            && !self.attr_store.is_multiline(markup.to_untyped());
         let is_symmetric = repr.start_bound != Boundary::Nil && repr.end_bound != Boundary::Nil;
         let get_delim = |bound: Boundary| {
             if scope == MarkupScope::Document || scope.can_trim() {
@@ -225,7 +255,7 @@ impl<'a> PrettyPrinter<'a> {
                 }
                 Boundary::SpaceOrBreak(n) | Boundary::WeakSpaceOrBreak(n) => {
                     if is_symmetric && !ctx.break_suppressed || !prefer_tight {
-                        if self.config.wrap_text {
+                        if self.config().wrap_text.is_enabled() {
                             self.arena.line()
                         } else {
                             self.arena.hardline().flat_alt(self.arena.spaces(n))
@@ -233,7 +263,7 @@ impl<'a> PrettyPrinter<'a> {
                     } else if scope.can_trim() {
                         // the space can be safely eaten
                         self.arena.nil()
-                    } else if self.config.wrap_text {
+                    } else if self.config().wrap_text.is_enabled() {
                         self.arena.space()
                     } else {
                         self.arena.spaces(n)
@@ -245,19 +275,219 @@ impl<'a> PrettyPrinter<'a> {
         body.enclose(get_delim(repr.start_bound), get_delim(repr.end_bound))
     }
 
+    /// Removes lines that only contain an import whose items are all unused.
+    fn remove_unused_import_lines(&self, repr: &mut MarkupRepr<'a>) {
+        let mut i = 0;
+        while i < repr.lines.len() {
+            let line = &repr.lines[i];
+            let is_unused = !line.nodes.iter().any(|node| is_comment_node(node))
+                && self
+                    .line_import(line)
+                    .is_some_and(|import| self.attr_store.is_unused_import(import.to_untyped()));
+            if !is_unused {
+                i += 1;
+                continue;
+            }
+            let removed = repr.lines.remove(i);
+            let is_last = i == repr.lines.len();
+            if let Some(prev) = i.checked_sub(1).and_then(|j| repr.lines.get_mut(j)) {
+                // Keep blank lines around the removed line, but not after the last line.
+                prev.breaks = if is_last {
+                    removed.breaks
+                } else {
+                    prev.breaks.max(removed.breaks)
+                };
+            }
+        }
+    }
+
+    /// Sorts runs of consecutive import lines, with packages first, and merges duplicate imports
+    /// of the same module. Comment lines between imports are moved with the next import, while
+    /// those before the first import stay in place.
+    fn reorder_import_lines(&self, repr: &mut MarkupRepr<'a>) {
+        let mut i = 0;
+        while i < repr.lines.len() {
+            // Each entry consists of comment lines followed by an import line.
+            let mut entries = vec![];
+            let mut entry_start = i;
+            let mut j = i;
+            while let Some(line) = repr.lines.get(j) {
+                if !line.nodes.is_empty() && is_comment_line(line) {
+                    if line.breaks != 1 {
+                        break;
+                    }
+                    if entries.is_empty() {
+                        entry_start = j + 1;
+                    }
+                } else if let Some(import) = self.line_import(line) {
+                    entries.push((entry_start..j + 1, import));
+                    entry_start = j + 1;
+                    if line.breaks != 1 {
+                        break;
+                    }
+                } else {
+                    break;
+                }
+                j += 1;
+            }
+            i = match entries.last() {
+                Some((last, _)) => last.end,
+                None => j + 1,
+            };
+            if entries.len() >= 2 && self.can_reorder_imports(entries.iter().map(|(_, it)| *it)) {
+                i = self.reorder_import_entries(repr, entries);
+            }
+        }
+    }
+
+    /// Reorders the entries of a run of imports, and returns the end of the run afterwards.
+    fn reorder_import_entries(
+        &self,
+        repr: &mut MarkupRepr<'a>,
+        mut entries: Vec<(std::ops::Range<usize>, ModuleImport<'a>)>,
+    ) -> usize {
+        let start = entries[0].0.start;
+        let end = entries.last().expect("non-empty entries").0.end;
+        let last_breaks = repr.lines[end - 1].breaks;
+        let mut lines = repr
+            .lines
+            .splice(start..end, [])
+            .map(Some)
+            .collect::<Vec<_>>();
+
+        entries.sort_by_cached_key(|(_, import)| import_sort_key(*import));
+
+        let mut output = Vec::<MarkupLine<'a>>::with_capacity(lines.len());
+        // The kept imports, with the index of their lines in the output and whether they have
+        // comments.
+        let mut kept = Vec::<(ModuleImport<'a>, usize, bool)>::new();
+        for (range, import) in entries {
+            // Imports with comments are kept as they are, so that no comment is lost or moved.
+            let has_comments = range.len() > 1
+                || lines[range.end - 1 - start]
+                    .as_ref()
+                    .is_some_and(|line| line.nodes.iter().any(|node| is_comment_node(node)));
+            let target = kept.iter().find(|(prev, _, prev_has_comments)| {
+                !has_comments && !prev_has_comments && self.can_merge_imports(*prev, import)
+            });
+            if let Some(&(prev, index, _)) = target {
+                let line = &mut output[index];
+                for item in import_item_nodes(import) {
+                    let text = item.clone().into_text();
+                    let is_duplicate = import_item_nodes(prev)
+                        .chain(line.merged_imports.iter().copied())
+                        .any(|it| it.clone().into_text() == text);
+                    if !is_duplicate {
+                        line.merged_imports.push(item);
+                    }
+                }
+                continue;
+            }
+            let is_duplicate = kept
+                .iter()
+                .any(|(prev, ..)| prev.to_untyped().spanless_eq(import.to_untyped()));
+            if !has_comments && is_duplicate {
+                continue;
+            }
+            for line in &mut lines[range.start - start..range.end - start] {
+                output.push(line.take().expect("each line is taken once"));
+            }
+            kept.push((import, output.len() - 1, has_comments));
+        }
+
+        for line in output.iter_mut() {
+            line.breaks = 1;
+        }
+        output.last_mut().expect("non-empty output").breaks = last_breaks;
+        let output_end = start + output.len();
+        repr.lines.splice(start..start, output);
+        output_end
+    }
+
+    /// Gets the import of a line that only contains an import statement and comments.
+    fn line_import(&self, line: &MarkupLine<'a>) -> Option<ModuleImport<'a>> {
+        let mut nodes = line
+            .nodes
+            .iter()
+            .filter(|node| node.kind() != SyntaxKind::Space && !is_comment_node(node));
+        match (nodes.next(), nodes.next(), nodes.next()) {
+            (Some(hash), Some(import), None)
+                if hash.kind() == SyntaxKind::Hash
+                    && !self.attr_store.is_format_disabled(import) =>
+            {
+                (*import).cast()
+            }
+            _ => None,
+        }
+    }
+
+    /// Enforces the configured number of blank lines around headings and block elements.
+    ///
+    /// Only existing paragraph breaks are adjusted, and they are kept as at least one blank line,
+    /// as adding or removing them could change the layout.
+    fn normalize_blank_lines(&self, repr: &mut MarkupRepr<'a>) {
+        let config = self.config();
+        let spacing = |line: &MarkupLine| match line_block_kind(line) {
+            Some(LineBlockKind::Heading) => (
+                config.blank_lines_before_heading,
+                config.blank_lines_after_heading,
+            ),
+            Some(LineBlockKind::Block) => (
+                config.blank_lines_around_block,
+                config.blank_lines_around_block,
+            ),
+            None => (None, None),
+        };
+        for i in 1..repr.lines.len() {
+            let prev = &repr.lines[i - 1];
+            // Comments on their own lines are attached to the next item, so the spacing before
+            // the item goes before its first comment.
+            if is_comment_line(prev) || prev.breaks < 2 {
+                continue;
+            }
+            let attached = repr.lines[i..]
+                .iter()
+                .position(|line| !is_comment_line(line) || line.breaks != 1)
+                .map(|n| &repr.lines[i + n]);
+            let before = attached
+                .filter(|next| !is_comment_line(next))
+                .and_then(|next| spacing(next).0);
+            let blank_lines = match (spacing(prev).1, before) {
+                (Some(a), Some(b)) => a.max(b),
+                (Some(n), None) | (None, Some(n)) => n,
+                (None, None) => continue,
+            };
+            repr.lines[i - 1].breaks = blank_lines.max(1) + 1;
+        }
+    }
+
     fn convert_markup_body(&'a self, ctx: Context, repr: &MarkupRepr<'a>) -> ArenaDoc<'a> {
         let mut doc = self.arena.nil();
         for &MarkupLine {
             ref nodes,
             breaks,
             mixed_text,
+            ref merged_imports,
         } in repr.lines.iter()
         {
-            for node in nodes.iter() {
-                doc += if node.kind() == SyntaxKind::Space {
+            let mut region_left = 0;
+            for (j, node) in nodes.iter().enumerate() {
+                if region_left > 0 {
+                    region_left -= 1;
+                    continue;
+                }
+                doc += if let Some(len) = self.attr_store.disabled_region_len(node) {
+                    region_left = len;
+                    self.convert_verbatim_nodes(&nodes[j..=j + len])
+                } else if node.kind() == SyntaxKind::Space {
                     self.convert_space_untyped(ctx, node)
                 } else if let Some(text) = node.cast::<Text>() {
                     self.convert_text(text)
+                } else if let Some(import) = node
+                    .cast::<ModuleImport>()
+                    .filter(|_| !merged_imports.is_empty())
+                {
+                    self.convert_import_merged(ctx, import, merged_imports)
                 } else if let Some(expr) = node.cast::<Expr>() {
                     let ctx = if mixed_text {
                         ctx.suppress_breaks()
@@ -267,6 +497,9 @@ impl<'a> PrettyPrinter<'a> {
                     self.convert_expr(ctx, expr)
                 } else if is_comment_node(node) {
                     self.convert_comment(ctx, node)
+                } else if node.kind() == SyntaxKind::Error {
+                    // Only in error-tolerant formatting.
+                    self.convert_verbatim_untyped(node)
                 } else {
                     // can be Hash, Semicolon, Shebang
                     self.convert_trivia_untyped(node)
@@ -331,13 +564,41 @@ impl<'a> PrettyPrinter<'a> {
                 || len == 2 && nodes[0].kind() == SyntaxKind::Hash
         }
 
+        // In sentence mode, lines are broken only at sentence ends, and joined otherwise.
+        // In unwrap mode, lines are always joined.
+        let mode = self.config().wrap_text;
+        // Checks whether the nodes end with a sentence, looking through closing quotes.
+        let ends_sentence = |nodes: &[&SyntaxNode]| {
+            nodes
+                .iter()
+                .rfind(|node| node.kind() != SyntaxKind::SmartQuote)
+                .and_then(|node| node.cast::<Text>())
+                .is_some_and(|text| self.text_ends_sentence(text))
+        };
+        let soft_break = |prev: &[&SyntaxNode]| match mode {
+            WrapText::Sentence if ends_sentence(prev) => self.arena.hardline(),
+            WrapText::Sentence | WrapText::Unwrap => self.arena.space(),
+            WrapText::Fill | WrapText::Off => self.arena.softline(),
+        };
+
         let mut doc = self.arena.nil();
         for (i, line) in repr.lines.iter().enumerate() {
             let &MarkupLine {
-                ref nodes, breaks, ..
+                ref nodes,
+                breaks,
+                ref merged_imports,
+                ..
             } = line;
+            let mut region_left = 0;
             for (j, node) in nodes.iter().enumerate() {
-                doc += if node.kind() == SyntaxKind::Space {
+                if region_left > 0 {
+                    region_left -= 1;
+                    continue;
+                }
+                doc += if let Some(len) = self.attr_store.disabled_region_len(node) {
+                    region_left = len;
+                    self.convert_verbatim_nodes(&nodes[j..=j + len])
+                } else if node.kind() == SyntaxKind::Space {
                     if nodes.get(j + 1).is_some_and(cannot_break_before) {
                         self.arena.space()
                     } else if nodes.get(j + 1).is_some_and(prefer_exclusive)
@@ -345,14 +606,26 @@ impl<'a> PrettyPrinter<'a> {
                     {
                         self.arena.hardline()
                     } else {
-                        self.arena.softline()
+                        soft_break(&nodes[..j])
                     }
                 } else if let Some(text) = node.cast::<Text>() {
-                    self.convert_text_wrapped(text)
+                    match mode {
+                        WrapText::Sentence => self.convert_text_sentences(text),
+                        WrapText::Unwrap => self.convert_text(text),
+                        WrapText::Fill | WrapText::Off => self.convert_text_wrapped(text),
+                    }
+                } else if let Some(import) = node
+                    .cast::<ModuleImport>()
+                    .filter(|_| !merged_imports.is_empty())
+                {
+                    self.convert_import_merged(ctx, import, merged_imports)
                 } else if let Some(expr) = node.cast::<Expr>() {
                     self.convert_expr(ctx, expr)
                 } else if is_comment_node(node) {
                     self.convert_comment(ctx, node)
+                } else if node.kind() == SyntaxKind::Error {
+                    // Only in error-tolerant formatting.
+                    self.convert_verbatim_untyped(node)
                 } else {
                     // can be Hash, Semicolon, Shebang
                     self.convert_trivia_untyped(node)
@@ -366,8 +639,9 @@ impl<'a> PrettyPrinter<'a> {
                     .is_some_and(|last| should_break_after(last) || preserve_break_after(last))
                 && !preserve_exclusive(line)
                 && !preserve_exclusive(&repr.lines[i + 1])
+                && (mode == WrapText::Unwrap || !is_cjk_linebreak(line, &repr.lines[i + 1]))
             {
-                doc += self.arena.softline();
+                doc += soft_break(nodes);
             } else if breaks > 0 {
                 doc += self.arena.hardline().repeat_n(breaks);
             }
@@ -381,6 +655,8 @@ struct MarkupLine<'a> {
     nodes: SmallVec<[&'a SyntaxNode; 4]>,
     breaks: usize,
     mixed_text: bool,
+    /// Import items merged into the import of this line from duplicate imports.
+    merged_imports: Vec<&'a SyntaxNode>,
 }
 
 struct MarkupRepr<'a> {
@@ -426,14 +702,24 @@ impl Boundary {
 // Break markup into lines, split by stmt, parbreak, newline, multiline raw,
 // equation if a line contains text, it will be skipped by the formatter
 // to keep the original format.
-fn collect_markup_repr(markup: Markup<'_>) -> MarkupRepr {
+fn collect_markup_repr<'a>(markup: Markup<'a>, attr_store: &AttrStore) -> MarkupRepr<'a> {
     let mut repr = MarkupRepr {
         lines: vec![],
         start_bound: Boundary::Nil,
         end_bound: Boundary::Nil,
     };
     let mut current_line = MarkupLine::default();
+    // Nodes in a region where formatting is disabled are kept in the line of its start.
+    let mut region_left = 0;
     for node in markup.to_untyped().children() {
+        if region_left > 0 {
+            current_line.nodes.push(node);
+            region_left -= 1;
+            continue;
+        }
+        if let Some(len) = attr_store.disabled_region_len(node) {
+            region_left = len;
+        }
         let break_line = match node.kind() {
             SyntaxKind::Parbreak => {
                 current_line.breaks = node.text().count_linebreaks(); // This is >= 2
@@ -522,6 +808,60 @@ fn collect_markup_repr(markup: Markup<'_>) -> MarkupRepr {
     repr
 }
 
+/// The kinds of lines around which blank lines can be normalized.
+enum LineBlockKind {
+    Heading,
+    /// Block equations, raw blocks and figures.
+    Block,
+}
+
+fn line_block_kind(line: &MarkupLine) -> Option<LineBlockKind> {
+    let mut nodes = line
+        .nodes
+        .iter()
+        .filter(|node| !matches!(node.kind(), SyntaxKind::Space | SyntaxKind::Label));
+    let kind = match (nodes.next()?, nodes.next()) {
+        (node, None) if node.kind() == SyntaxKind::Heading => LineBlockKind::Heading,
+        (node, None) if is_block_equation(node) || is_block_raw(node) => LineBlockKind::Block,
+        (hash, Some(call)) if hash.kind() == SyntaxKind::Hash && is_figure_call(call) => {
+            LineBlockKind::Block
+        }
+        _ => return None,
+    };
+    nodes.next().is_none().then_some(kind)
+}
+
+/// Checks whether a linebreak is between CJK characters. Typst renders it as a space, so joining
+/// the lines would insert a space that the author did not write, and breaking lines between CJK
+/// characters elsewhere would add spaces to the output.
+fn is_cjk_linebreak(line: &MarkupLine, next: &MarkupLine) -> bool {
+    let is_cjk_text = |node: Option<&&SyntaxNode>, last: bool| {
+        node.filter(|node| node.kind() == SyntaxKind::Text)
+            .and_then(|node| {
+                let mut chars = node.text().chars();
+                if last {
+                    chars.next_back()
+                } else {
+                    chars.next()
+                }
+            })
+            .is_some_and(is_cjk)
+    };
+    is_cjk_text(line.nodes.last(), true) && is_cjk_text(next.nodes.first(), false)
+}
+
+fn is_comment_line(line: &MarkupLine) -> bool {
+    line.nodes
+        .iter()
+        .all(|node| is_comment_node(node) || node.kind() == SyntaxKind::Space)
+}
+
+fn is_figure_call(it: &SyntaxNode) -> bool {
+    it.cast::<FuncCall>().is_some_and(
+        |call| matches!(call.callee(), Expr::Ident(ident) if ident.as_str() == "figure"),
+    )
+}
+
 fn is_block_elem(it: &SyntaxNode) -> bool {
     matches!(
         it.kind(),
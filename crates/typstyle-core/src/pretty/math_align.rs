@@ -40,7 +40,7 @@ impl<'a> PrettyPrinter<'a> {
             .unwrap_or_default();
 
         // Early‑exit if even the empty grid would exceed max width
-        if col_num > self.config.max_width {
+        if col_num > self.config().max_width {
             return None;
         }
 
@@ -61,7 +61,7 @@ impl<'a> PrettyPrinter<'a> {
 
                         let mut buf = String::new();
                         self.convert_math_children(ctx, cell_nodes.into_iter())
-                            .render_fmt(self.config.max_width, &mut buf)
+                            .render_fmt(self.config().max_width, &mut buf)
                             .ok()?;
                         if ends_with_line_comment {
                             buf.push_str("\n "); // ensure an extra line is added
@@ -94,7 +94,7 @@ impl<'a> PrettyPrinter<'a> {
                         if cell_width > col_widths[j] {
                             grid_width += cell_width - col_widths[j];
                             col_widths[j] = cell_width;
-                            if grid_width > self.config.max_width {
+                            if grid_width > self.config().max_width {
                                 return None; // bail out
                             }
                         }
@@ -19,15 +19,24 @@ mod table;
 mod text;
 mod util;
 
+use std::{cell::RefCell, rc::Rc};
+
+use context::AlignMode;
 pub use context::{Context, Mode};
 use prelude::*;
 use style::FoldStyle;
 use typst_syntax::{ast::*, SyntaxNode};
 
-use crate::{ext::StrExt, AttrStore, Config};
+use crate::{
+    attr::{LayoutFeature, Overrides},
+    config::IndentStyle,
+    ext::StrExt,
+    render, AttrStore, Config,
+};
 
 pub struct PrettyPrinter<'a> {
-    config: Config,
+    /// The config in effect, which directives may change while converting the next item.
+    config: RefCell<Rc<Config>>,
     attr_store: AttrStore,
     arena: Arena<'a>,
 }
@@ -35,14 +44,26 @@ pub struct PrettyPrinter<'a> {
 impl<'a> PrettyPrinter<'a> {
     pub fn new(config: Config, attr_store: AttrStore) -> Self {
         Self {
-            config,
+            config: RefCell::new(Rc::new(config)),
             attr_store,
             arena: Arena::new(),
         }
     }
 
-    pub fn config(&self) -> &Config {
-        &self.config
+    pub fn config(&self) -> Rc<Config> {
+        self.config.borrow().clone()
+    }
+
+    /// Runs `f` with the config replaced, restoring the current one afterwards.
+    fn with_config<T>(&self, config: Rc<Config>, f: impl FnOnce() -> T) -> T {
+        let outer = self.config.replace(config);
+        let result = f();
+        self.config.replace(outer);
+        result
+    }
+
+    pub fn attr_store(&self) -> &AttrStore {
+        &self.attr_store
     }
 
     fn get_fold_style(&self, ctx: Context, node: impl AstNode<'a>) -> FoldStyle {
@@ -81,13 +102,28 @@ impl<'a> PrettyPrinter<'a> {
 
     /// For inner or lead nodes.
     fn convert_verbatim_untyped(&'a self, node: &'a SyntaxNode) -> ArenaDoc<'a> {
-        let text = node.clone().into_text();
+        self.convert_verbatim_text(node.clone().into_text().to_string())
+    }
+
+    /// For a run of siblings, such as a region where formatting is disabled.
+    fn convert_verbatim_nodes(&'a self, nodes: &[&'a SyntaxNode]) -> ArenaDoc<'a> {
+        let mut text = String::new();
+        for node in nodes {
+            text.push_str(&(*node).clone().into_text());
+        }
+        self.convert_verbatim_text(text)
+    }
+
+    fn convert_verbatim_text(&'a self, text: String) -> ArenaDoc<'a> {
         if !text.has_linebreak() {
-            return self.arena.text(text.to_string());
+            return self.arena.text(text);
         }
         // When the text spans multiple lines, we should split it to ensure proper fitting.
+        // Unlike `str::lines`, a trailing linebreak is kept, such as in an erroneous node.
         let doc = self.arena.intersperse(
-            node.clone().into_text().lines().map(str::to_string),
+            text.split('\n')
+                .map(|line| line.strip_suffix('\r').unwrap_or(line).to_string())
+                .collect::<Vec<_>>(),
             self.arena.hardline(),
         );
         self.arena
@@ -108,9 +144,61 @@ impl<'a> PrettyPrinter<'a> {
         if let Some(res) = self.check_disabled(expr.to_untyped()) {
             return res;
         }
+        if let Some(overrides) = self.attr_store.overrides(expr.to_untyped()) {
+            return self.convert_expr_overridden(ctx, expr, overrides);
+        }
         self.convert_expr_impl(ctx, expr)
     }
 
+    /// Converts an expression with the changes given by a directive before it.
+    fn convert_expr_overridden(
+        &'a self,
+        ctx: Context,
+        expr: Expr<'a>,
+        overrides: &Overrides,
+    ) -> ArenaDoc<'a> {
+        let ctx = overrides
+            .skipped
+            .iter()
+            .fold(ctx, |ctx, feature| match feature {
+                LayoutFeature::Table => ctx.skip_table(),
+                LayoutFeature::MathAlign => ctx.aligned(AlignMode::Never),
+            });
+        if overrides.options.is_empty() {
+            return self.convert_expr_impl(ctx, expr);
+        }
+        let outer = self.config();
+        let mut config = (*outer).clone();
+        for (key, value) in &overrides.options {
+            // Invalid options are ignored.
+            let _ = config.set_option(key, value);
+        }
+        let renders_alike = config.max_width == outer.max_width
+            && config.indent_style == outer.indent_style
+            && (config.indent_style == IndentStyle::Space || config.tab_spaces == outer.tab_spaces);
+        let config = Rc::new(config);
+        let doc = self.with_config(config.clone(), || self.convert_expr_impl(ctx, expr));
+        if renders_alike {
+            return doc;
+        }
+        // The width and indentation style can not vary within a document, so the expression is
+        // rendered separately, from the cursor and with the indentation where it is placed.
+        self.arena.column(move |column| {
+            let (doc, config) = (doc.clone(), config.clone());
+            self.arena
+                .nesting(move |indent| {
+                    // Spaces take the place of the text before the cursor, and are removed after.
+                    let doc = (self.arena.spaces(column) + doc.clone()).nest(indent as isize);
+                    match render::render_doc(&doc, &config) {
+                        Ok(text) => self.convert_verbatim_text(text[column..].to_string()),
+                        Err(_) => self.convert_verbatim(expr),
+                    }
+                    .into_doc()
+                })
+                .into_doc()
+        })
+    }
+
     fn convert_expr_impl(&'a self, ctx: Context, expr: Expr<'a>) -> ArenaDoc<'a> {
         match expr {
             Expr::Text(t) => self.convert_text(t),
@@ -46,7 +46,7 @@ impl<'a> PrettyPrinter<'a> {
         optional_paren(
             &self.arena,
             self.convert_expr(ctx, expr),
-            self.config.tab_spaces,
+            self.config().tab_spaces,
             delims,
         )
     }
@@ -66,7 +66,7 @@ impl<'a> PrettyPrinter<'a> {
         // - If without paren, the entire expression is in one line, thus safe.
         // - If with paren, surely safe.
         let ctx = ctx.with_mode(Mode::CodeCont);
-        optional_paren(&self.arena, body(ctx), self.config.tab_spaces, ("(", ")"))
+        optional_paren(&self.arena, body(ctx), self.config().tab_spaces, ("(", ")"))
     }
 }
 
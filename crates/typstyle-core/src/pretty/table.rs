@@ -39,8 +39,15 @@ impl<'a> PrettyPrinter<'a> {
         // - reflow cells if no special cells (cell, hline, vline, )
         // - hard break at linebreaks with at least 1 empty lines
         let can_reflow_cells = table.args().items().any(is_special_cell);
+        let has_trailing_sep = get_parenthesized_args_untyped(table.args())
+            .filter(|node| !node.kind().is_trivia())
+            .last()
+            .is_some_and(|node| node.kind() == SyntaxKind::Comma);
+        let (trailing_sep_flat, trailing_sep_break) =
+            self.config().trailing_comma.resolve(has_trailing_sep);
         let mut collector =
-            TableCollector::new(&self.arena, if can_reflow_cells { 0 } else { columns });
+            TableCollector::new(&self.arena, if can_reflow_cells { 0 } else { columns })
+                .with_trailing_sep(trailing_sep_flat, trailing_sep_break);
 
         for node in get_parenthesized_args_untyped(table.args()) {
             if let Some(arg) = node.cast::<Arg>() {
@@ -69,7 +76,7 @@ impl<'a> PrettyPrinter<'a> {
         let doc = collector.collect();
         doc.enclose(self.arena.line_(), self.arena.line_())
             .group()
-            .nest(self.config.tab_spaces as isize)
+            .nest(self.config().tab_spaces as isize)
             .parens()
     }
 }
@@ -13,6 +13,58 @@ impl<'a> PrettyPrinter<'a> {
         wrap_text(&self.arena, text.get())
     }
 
+    /// Converts text with each sentence ending at a linebreak, and other words joined by spaces.
+    pub(super) fn convert_text_sentences(&'a self, text: Text<'a>) -> ArenaDoc<'a> {
+        let text = text.get();
+        let mut doc = self.arena.nil();
+        let mut words = text.split_ascii_whitespace().peekable();
+        while let Some(word) = words.next() {
+            doc += self.arena.text(word);
+            match words.peek() {
+                Some(next) if self.ends_sentence(word) && can_start_line(next) => {
+                    doc += self.arena.hardline();
+                }
+                Some(_) => doc += self.arena.space(),
+                // special case when a link follows the text
+                None if text.ends_with(' ') && self.ends_sentence(word) => {
+                    doc += self.arena.hardline();
+                }
+                None if text.ends_with(' ') => doc += self.arena.space(),
+                None => {}
+            }
+        }
+        doc
+    }
+
+    /// Checks whether the text ends with a sentence.
+    pub(super) fn text_ends_sentence(&self, text: Text) -> bool {
+        text.get()
+            .split_ascii_whitespace()
+            .next_back()
+            .is_some_and(|word| self.ends_sentence(word))
+    }
+
+    fn ends_sentence(&self, word: &str) -> bool {
+        let word = word.trim_end_matches([')', ']', '"', '\'', '”', '’']);
+        word.ends_with(['.', '!', '?', '…']) && !self.is_abbreviation(word)
+    }
+
+    fn is_abbreviation(&self, word: &str) -> bool {
+        let word = word.trim_start_matches(['(', '[', '"', '\'', '“', '‘']);
+        // Initials, such as `J.` in `J. Doe`
+        let mut chars = word.chars();
+        if matches!(
+            (chars.next(), chars.next(), chars.next()),
+            (Some(c), Some('.'), None) if c.is_uppercase()
+        ) {
+            return true;
+        }
+        self.config()
+            .sentence_abbreviations
+            .iter()
+            .any(|abbr| abbr == word)
+    }
+
     pub(super) fn convert_space(&'a self, ctx: Context, space: Space<'a>) -> ArenaDoc<'a> {
         self.convert_space_untyped(ctx, space.to_untyped())
     }
@@ -24,7 +76,7 @@ impl<'a> PrettyPrinter<'a> {
     ) -> ArenaDoc<'a> {
         if node.text().has_linebreak() {
             self.arena.hardline()
-        } else if ctx.mode.is_markup() && !self.config.collapse_markup_spaces {
+        } else if ctx.mode.is_markup() && !self.config().collapse_markup_spaces {
             self.arena.text(node.text().as_str())
         } else {
             self.arena.space()
@@ -37,6 +89,16 @@ impl<'a> PrettyPrinter<'a> {
     }
 }
 
+/// Checks whether a word can start a line, without being parsed as a markup marker.
+fn can_start_line(word: &str) -> bool {
+    let is_marker = matches!(word, "+" | "-" | "/")
+        || word.chars().all(|c| c == '=')
+        || word
+            .strip_suffix('.')
+            .is_some_and(|num| !num.is_empty() && num.bytes().all(|b| b.is_ascii_digit()));
+    !is_marker
+}
+
 fn wrap_text<'a>(arena: &'a Arena<'a>, text: &'a str) -> ArenaDoc<'a> {
     arena.intersperse(text.split_ascii_whitespace(), arena.softline())
         + if text.ends_with(' ') {
@@ -46,3 +46,19 @@ pub(super) fn get_parenthesized_args_untyped(node: Args<'_>) -> impl Iterator<It
 pub(super) fn get_parenthesized_args(node: Args<'_>) -> impl Iterator<Item = Arg<'_>> {
     get_parenthesized_args_untyped(node).filter_map(|node| node.cast::<Arg>())
 }
+
+/// Checks whether the character is a Chinese or Japanese character or punctuation, which are not
+/// separated by spaces.
+pub fn is_cjk(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3000}'..='\u{303F}' // CJK Symbols and Punctuation
+            | '\u{3040}'..='\u{30FF}' // Hiragana and Katakana
+            | '\u{31F0}'..='\u{31FF}' // Katakana Phonetic Extensions
+            | '\u{3400}'..='\u{4DBF}' // CJK Unified Ideographs Extension A
+            | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+            | '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+            | '\u{FF00}'..='\u{FFEF}' // Halfwidth and Fullwidth Forms
+            | '\u{20000}'..='\u{3FFFF}' // CJK Unified Ideographs Extension B and later
+    )
+}
@@ -0,0 +1,80 @@
+use std::fmt;
+
+use pretty::{Render, RenderAnnotated};
+
+use crate::{config::IndentStyle, pretty::prelude::ArenaDoc, Config};
+
+/// Renders the document to a string, writing indentation in the configured style.
+///
+/// The layout is computed with indentation in columns, where a tab counts as `tab_spaces`
+/// columns, so that widths are checked correctly against `max_width`.
+pub fn render_doc(doc: &ArenaDoc<'_>, config: &Config) -> Result<String, fmt::Error> {
+    let mut writer = IndentWriter {
+        out: String::new(),
+        style: config.indent_style,
+        tab_width: config.tab_spaces,
+        indent: None,
+    };
+    doc.render_raw(config.max_width, &mut writer)?;
+    writer.flush_indent();
+    Ok(writer.out)
+}
+
+/// A writer that collects the indentation written after each line break, and emits it
+/// when the line content starts.
+struct IndentWriter {
+    out: String,
+    style: IndentStyle,
+    tab_width: usize,
+    /// The width of the pending indentation, if at the start of a line.
+    indent: Option<usize>,
+}
+
+impl IndentWriter {
+    fn flush_indent(&mut self) {
+        let Some(width) = self.indent.take() else {
+            return;
+        };
+        let (tabs, spaces) = match self.style {
+            IndentStyle::Space => (0, width),
+            IndentStyle::Tab if self.tab_width == 0 => (0, width),
+            IndentStyle::Tab => (width / self.tab_width, width % self.tab_width),
+        };
+        self.out.extend(std::iter::repeat_n('\t', tabs));
+        self.out.extend(std::iter::repeat_n(' ', spaces));
+    }
+}
+
+impl Render for IndentWriter {
+    type Error = fmt::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<usize, Self::Error> {
+        // The printer writes indentation separately from texts, as runs of spaces.
+        if let Some(width) = &mut self.indent {
+            if !s.is_empty() && s.bytes().all(|b| b == b' ') {
+                *width += s.len();
+                return Ok(s.len());
+            }
+        }
+        self.flush_indent();
+        self.out.push_str(s);
+        if s.ends_with('\n') {
+            self.indent = Some(0);
+        }
+        Ok(s.len())
+    }
+
+    fn fail_doc(&self) -> Self::Error {
+        fmt::Error
+    }
+}
+
+impl<A> RenderAnnotated<'_, A> for IndentWriter {
+    fn push_annotation(&mut self, _: &A) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn pop_annotation(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
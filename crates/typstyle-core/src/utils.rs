@@ -1,18 +1,26 @@
 use std::ops::Range;
 
-/// Strip trailing whitespace in each line of the input string.
-pub fn strip_trailing_whitespace(s: &str) -> String {
+/// Strip trailing whitespace in each line of the input string, ending each line with `newline`.
+pub fn strip_trailing_whitespace(s: &str, newline: &str) -> String {
     if s.is_empty() {
-        return "\n".to_string();
+        return newline.to_string();
     }
     let mut res = String::with_capacity(s.len());
     for line in s.lines() {
         res.push_str(line.trim_end());
-        res.push('\n');
+        res.push_str(newline);
     }
     res
 }
 
+/// Replace the line endings of the input string with `newline`.
+pub fn replace_newlines(s: String, newline: &str) -> String {
+    if newline == "\n" && !s.contains('\r') {
+        return s;
+    }
+    s.replace("\r\n", "\n").replace('\n', newline)
+}
+
 /// Get the range of the string obtained from trimming in the original string.
 pub fn trim_range(s: &str, mut rng: Range<usize>) -> Range<usize> {
     rng.end = rng.start + s[rng.clone()].trim_end().len();
@@ -20,7 +28,9 @@ pub fn trim_range(s: &str, mut rng: Range<usize>) -> Range<usize> {
     rng
 }
 
-pub fn count_spaces_after_last_newline(s: &str, i: usize) -> usize {
+/// Count the width of the indentation of the line containing position `i`,
+/// where a tab counts as `tab_width` columns.
+pub fn count_indent_after_last_newline(s: &str, i: usize, tab_width: usize) -> usize {
     // Ensure the byte position `i` is a valid UTF-8 boundary
     debug_assert!(
         s.is_char_boundary(i),
@@ -31,8 +41,15 @@ pub fn count_spaces_after_last_newline(s: &str, i: usize) -> usize {
     if let Some(pos) = s[..i].rfind('\n') {
         // Get the substring after the newline and up to position `i`
         let after_newline = &s[pos + 1..i];
-        // Count the number of consecutive spaces in the substring
-        after_newline.chars().take_while(|&c| c == ' ').count()
+        // Count the width of consecutive spaces and tabs in the substring
+        after_newline
+            .chars()
+            .map_while(|c| match c {
+                ' ' => Some(1),
+                '\t' => Some(tab_width),
+                _ => None,
+            })
+            .sum()
     } else {
         // If no newline is found, return 0
         0
@@ -45,15 +62,25 @@ mod tests {
 
     #[test]
     fn test_strip_trailing_whitespace() {
-        let s = strip_trailing_whitespace("");
+        let s = strip_trailing_whitespace("", "\n");
         assert_eq!(s, "\n");
-        let s = strip_trailing_whitespace(" ");
+        let s = strip_trailing_whitespace(" ", "\n");
         assert_eq!(s, "\n");
-        let s = strip_trailing_whitespace("\n");
+        let s = strip_trailing_whitespace("\n", "\n");
         assert_eq!(s, "\n");
-        let s = strip_trailing_whitespace(" \n - \n");
+        let s = strip_trailing_whitespace(" \n - \n", "\n");
         assert_eq!(s, "\n -\n");
-        let s = strip_trailing_whitespace(" \n - \n ");
+        let s = strip_trailing_whitespace(" \n - \n ", "\n");
         assert_eq!(s, "\n -\n\n");
+        let s = strip_trailing_whitespace(" \r\n - \n", "\r\n");
+        assert_eq!(s, "\r\n -\r\n");
+    }
+
+    #[test]
+    fn test_replace_newlines() {
+        let s = replace_newlines("a\nb\r\nc".to_string(), "\n");
+        assert_eq!(s, "a\nb\nc");
+        let s = replace_newlines("a\nb\r\nc".to_string(), "\r\n");
+        assert_eq!(s, "a\r\nb\r\nc");
     }
 }
@@ -1,6 +1,6 @@
 use std::{env, fs, path::Path};
 
-use syn::{Attribute, FieldsNamed, Item, ItemStruct, Lit, Meta, Type};
+use syn::{Attribute, FieldsNamed, Item, ItemEnum, ItemStruct, Lit, Meta, Type};
 
 fn main() {
     // Define the path to the Config struct in the typstyle-core crate.
@@ -19,7 +19,7 @@ fn main() {
     let config_struct_option = find_config_struct(&ast);
 
     let ts_interface_fields = if let Some(config_struct) = config_struct_option {
-        generate_ts_fields_for_config_struct(config_struct)
+        generate_ts_fields_for_config_struct(config_struct, &find_enums(&ast))
     } else {
         eprintln!(
             "cargo:warning=Config struct not found in {}. Proceeding with an empty TypeScript interface.",
@@ -59,11 +59,22 @@ fn find_config_struct(ast: &syn::File) -> Option<&ItemStruct> {
     None
 }
 
+/// Finds all enum definitions within a parsed Rust file AST.
+fn find_enums(ast: &syn::File) -> Vec<&ItemEnum> {
+    ast.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Enum(item_enum) => Some(item_enum),
+            _ => None,
+        })
+        .collect()
+}
+
 /// Generates TypeScript interface field definitions from a Rust struct's fields.
 /// Includes JSDoc comments extracted from Rust doc comments.
 ///
 /// Assume `Config` is at the top level.
-fn generate_ts_fields_for_config_struct(config_struct: &ItemStruct) -> String {
+fn generate_ts_fields_for_config_struct(config_struct: &ItemStruct, enums: &[&ItemEnum]) -> String {
     let mut ts_fields_string = String::new();
 
     // Optional: Extract and add doc comments for the interface itself
@@ -74,10 +85,11 @@ fn generate_ts_fields_for_config_struct(config_struct: &ItemStruct) -> String {
         for field in named {
             let field_doc_comments = extract_doc_comments(&field.attrs);
             if let (Some(field_ident), Some(ts_type)) =
-                (&field.ident, rust_type_to_ts_type(&field.ty))
+                (&field.ident, rust_type_to_ts_type(&field.ty, enums))
             {
                 ts_fields_string.push_str(&field_doc_comments);
-                ts_fields_string.push_str(&format!("    {field_ident}: {ts_type},\n"));
+                // Missing fields take their default values, as `Config` is `#[serde(default)]`.
+                ts_fields_string.push_str(&format!("    {field_ident}?: {ts_type},\n"));
             } else {
                 eprintln!(
                     "cargo:warning=Could not map type for field {:?} in Config struct. It will be omitted from the TypeScript interface.",
@@ -90,7 +102,9 @@ fn generate_ts_fields_for_config_struct(config_struct: &ItemStruct) -> String {
 }
 
 /// Converts a Rust type identifier to its corresponding TypeScript type string.
-fn rust_type_to_ts_type(ty: &Type) -> Option<String> {
+/// Enums of unit variants are mapped to unions of their lowercase names, and `Option<T>` is
+/// mapped to `T | null`, and `Vec<T>` to `T[]`.
+fn rust_type_to_ts_type(ty: &Type, enums: &[&ItemEnum]) -> Option<String> {
     if let Type::Path(type_path) = ty {
         if type_path.qself.is_none() {
             let last_segment = type_path.path.segments.last()?;
@@ -101,14 +115,55 @@ fn rust_type_to_ts_type(ty: &Type) -> Option<String> {
                 | "f32" | "f64" => Some("number".to_string()),
                 "bool" => Some("boolean".to_string()),
                 "String" => Some("string".to_string()),
+                "Option" => Some(format!(
+                    "{} | null",
+                    rust_type_to_ts_type(first_type_argument(&last_segment.arguments)?, enums)?
+                )),
+                "Vec" => {
+                    let inner =
+                        rust_type_to_ts_type(first_type_argument(&last_segment.arguments)?, enums)?;
+                    if inner.contains('|') {
+                        Some(format!("({inner})[]"))
+                    } else {
+                        Some(format!("{inner}[]"))
+                    }
+                }
                 // Add more mappings if your Config struct uses other types
-                _ => None,
+                _ => enums
+                    .iter()
+                    .find(|item_enum| item_enum.ident == ident_str)
+                    .and_then(|item_enum| enum_to_ts_union(item_enum)),
             };
         }
     }
     None
 }
 
+/// Gets the first generic type argument, such as `T` in `Option<T>`.
+fn first_type_argument(arguments: &syn::PathArguments) -> Option<&Type> {
+    match arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first()? {
+            syn::GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Converts an enum of unit variants to a union of string literals, following
+/// `#[serde(rename_all = "lowercase")]`.
+fn enum_to_ts_union(item_enum: &ItemEnum) -> Option<String> {
+    let variants = item_enum
+        .variants
+        .iter()
+        .map(|variant| {
+            matches!(variant.fields, syn::Fields::Unit)
+                .then(|| format!("\"{}\"", variant.ident.to_string().to_lowercase()))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(variants.join(" | "))
+}
+
 /// Extracts Rust doc comments (#[doc = "..."]) from attributes and formats them as JSDoc.
 fn extract_doc_comments(attrs: &[Attribute]) -> String {
     let mut doc_lines = Vec::new();
@@ -1,4 +1,5 @@
 use js_sys::Error;
+use serde::Serialize;
 use typstyle_core::{Config, Typstyle};
 use wasm_bindgen::prelude::*;
 
@@ -6,6 +7,25 @@ use wasm_bindgen::prelude::*;
 const TYPES: &'static str =
     include_str!(concat!(env!("OUT_DIR"), "/generated_config_interface.ts"));
 
+#[wasm_bindgen(typescript_custom_section)]
+const TEXT_EDIT_TYPE: &'static str = r#"
+export interface TextEdit {
+  /** The UTF-16 offset where the replaced range starts. */
+  start: number;
+  /** The UTF-16 offset where the replaced range ends. */
+  end: number;
+  /** The text to replace the range with. */
+  text: string;
+}"#;
+
+/// A text edit with offsets in UTF-16 code units, as used by JavaScript strings.
+#[derive(Serialize)]
+struct TextEdit {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
 #[wasm_bindgen(start)]
 pub fn run() {
     // When the `console_error_panic_hook` feature is enabled, we can call the
@@ -36,6 +56,35 @@ pub fn format(
     t.format_text(text).render().map_err(into_error)
 }
 
+/// Formats the content and returns the minimal edits against it, which are sorted
+/// and do not overlap.
+#[wasm_bindgen(unchecked_return_type = "TextEdit[]")]
+pub fn format_edits(
+    text: &str,
+    #[wasm_bindgen(unchecked_param_type = "Config")] config: JsValue,
+) -> Result<JsValue, Error> {
+    let config = parse_config(config)?;
+    let t = Typstyle::new(config);
+    let edits = t.format_text(text).render_edits().map_err(into_error)?;
+
+    // Convert byte offsets to UTF-16 offsets incrementally, as the edits are sorted.
+    let (mut byte_offset, mut utf16_offset) = (0, 0);
+    let mut to_utf16 = |offset: usize| {
+        utf16_offset += text[byte_offset..offset].encode_utf16().count();
+        byte_offset = offset;
+        utf16_offset
+    };
+    let edits = edits
+        .into_iter()
+        .map(|edit| TextEdit {
+            start: to_utf16(edit.range.start),
+            end: to_utf16(edit.range.end),
+            text: edit.replacement,
+        })
+        .collect::<Vec<_>>();
+    serde_wasm_bindgen::to_value(&edits).map_err(into_error)
+}
+
 /// Get the pretty IR for the content.
 #[wasm_bindgen]
 pub fn format_ir(
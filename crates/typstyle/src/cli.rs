@@ -1,6 +1,11 @@
-use std::{path::PathBuf, sync::LazyLock};
+use std::{num::NonZeroUsize, ops::RangeInclusive, path::PathBuf, sync::LazyLock};
 
-use clap::{error::ErrorKind, Args, CommandFactory, Parser, Subcommand};
+use clap::{
+    builder::{PossibleValuesParser, TypedValueParser},
+    error::ErrorKind,
+    Args, CommandFactory, Parser, Subcommand, ValueEnum,
+};
+use typstyle_core::{IndentStyle, NewlineStyle, WrapText};
 
 #[derive(Parser)]
 #[command(
@@ -24,6 +29,27 @@ pub struct CliArguments {
     #[arg(long, default_value_t = false, global = true)]
     pub check: bool,
 
+    /// Run in 'check' mode, and print a unified diff of the changes that would be made.
+    #[arg(long, default_value_t = false, conflicts_with = "inplace")]
+    pub diff: bool,
+
+    /// The format of the report printed in 'check' mode.
+    #[arg(
+        long,
+        value_enum,
+        default_value_t,
+        requires = "check",
+        conflicts_with = "diff"
+    )]
+    pub output_format: OutputFormat,
+
+    /// Number of files to format in parallel [default: number of CPUs]
+    #[arg(short, long, value_name = "N")]
+    pub jobs: Option<NonZeroUsize>,
+
+    #[command(flatten, next_help_heading = "File Selection")]
+    pub files: FileSelectionArgs,
+
     #[command(flatten, next_help_heading = "Format Configuration")]
     pub style: StyleArgs,
 
@@ -57,38 +83,160 @@ pub enum Command {
         #[arg(value_enum)]
         shell: clap_complete::Shell,
     },
+
+    /// Start a language server over stdio that provides formatting
+    Lsp,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Log the files that would be reformatted.
+    #[default]
+    Text,
+    /// Print a JSON report of all checked files.
+    Json,
+    /// Print a SARIF log, to be uploaded to code scanning services.
+    Sarif,
+    /// Print GitHub Actions workflow commands, which are shown as annotations.
+    Github,
+}
+
+#[derive(Args)]
+pub struct FileSelectionArgs {
+    /// Exclude files and directories matching the gitignore-style pattern when searching
+    /// directories. Can be specified multiple times.
+    #[arg(long, value_name = "PATTERN")]
+    pub exclude: Vec<String>,
+
+    /// Only format files matching the gitignore-style pattern when searching directories.
+    /// Can be specified multiple times.
+    #[arg(long, value_name = "PATTERN")]
+    pub include: Vec<String>,
+
+    /// Do not respect ignore files (`.gitignore`, `.ignore` and `.typstyleignore`).
+    #[arg(long, default_value_t = false)]
+    pub no_ignore: bool,
+
+    /// Only format the given range of lines in each input, e.g. `10-40`.
+    /// Can be specified multiple times.
+    #[arg(
+        long,
+        value_name = "START-END",
+        value_parser = parse_line_range,
+        conflicts_with = "changed_since"
+    )]
+    pub lines: Vec<RangeInclusive<usize>>,
+
+    /// Only format the lines changed since the given git revision, including uncommitted
+    /// changes. Untracked files are formatted entirely.
+    #[arg(long, value_name = "REV", requires = "input")]
+    pub changed_since: Option<String>,
+}
+
+/// Parses a 1-based inclusive line range like `10-40`, or a single line like `10`.
+fn parse_line_range(s: &str) -> Result<RangeInclusive<usize>, String> {
+    let (start, end) = s.split_once('-').unwrap_or((s, s));
+    let parse = |n: &str| {
+        n.trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|&n| n > 0)
+            .ok_or_else(|| format!("invalid line number `{n}`"))
+    };
+    let (start, end) = (parse(start)?, parse(end)?);
+    if start > end {
+        return Err(format!(
+            "the start line {start} is after the end line {end}"
+        ));
+    }
+    Ok(start..=end)
 }
 
 #[derive(Args)]
 pub struct StyleArgs {
-    /// Maximum width of each line.
+    /// Path to a configuration file. Disables the discovery of `typstyle.toml`
+    /// and `[tool.typstyle]` in `typst.toml`.
+    #[arg(long, global = true, conflicts_with = "no_config")]
+    pub config: Option<PathBuf>,
+
+    /// Ignore all configuration files.
+    #[arg(long, default_value_t = false, global = true)]
+    pub no_config: bool,
+
+    /// Maximum width of each line [default: 80]
     #[arg(
         short = 'l',
         long,
         visible_short_alias = 'c',
         visible_alias = "column",
-        default_value_t = 80,
         global = true
     )]
-    pub line_width: usize,
+    pub line_width: Option<usize>,
+
+    /// Number of spaces per indentation level [default: 2]
+    #[arg(short = 't', long, visible_alias = "tab-width", global = true)]
+    pub indent_width: Option<usize>,
 
-    /// Number of spaces per indentation level.
+    /// Indent with spaces or tabs [default: space]
     #[arg(
-        short = 't',
         long,
-        visible_alias = "tab-width",
-        default_value_t = 2,
+        value_name = "STYLE",
+        value_parser = PossibleValuesParser::new(["space", "tab"])
+            .map(|s| s.parse::<IndentStyle>().unwrap()),
         global = true
     )]
-    pub indent_width: usize,
+    pub indent_style: Option<IndentStyle>,
+
+    /// Line ending of the output. `auto` keeps the line ending of the input [default: auto]
+    #[arg(
+        long,
+        value_name = "STYLE",
+        value_parser = PossibleValuesParser::new(["auto", "lf", "crlf", "native"])
+            .map(|s| s.parse::<NewlineStyle>().unwrap()),
+        global = true
+    )]
+    pub newline_style: Option<NewlineStyle>,
+
+    /// Reorder import items alphabetically [default]
+    #[arg(long, overrides_with = "no_reorder_import_items", global = true)]
+    pub reorder_import_items: bool,
 
     /// Disable alphabetical reordering of import items.
-    #[arg(long, default_value_t = false, global = true)]
+    #[arg(long, overrides_with = "reorder_import_items", global = true)]
     pub no_reorder_import_items: bool,
 
-    /// Wrap text in markup to fit within the line width. Implies `--collapse-spaces`.
-    #[arg(long, default_value_t = false, global = true)]
-    pub wrap_text: bool,
+    /// Wrap text in markup to fit within the line width, put each sentence on its own line, or
+    /// join the lines of each paragraph. Implies `--collapse-spaces`.
+    #[arg(
+        long,
+        value_name = "MODE",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "fill",
+        value_parser = PossibleValuesParser::new(["off", "fill", "sentence", "unwrap"])
+            .map(|s| s.parse::<WrapText>().unwrap()),
+        global = true
+    )]
+    pub wrap_text: Option<WrapText>,
+
+    /// Format files with syntax errors, keeping the erroneous parts unchanged.
+    #[arg(long, overrides_with = "no_tolerate_errors", global = true)]
+    pub tolerate_errors: bool,
+
+    /// Leave files with syntax errors unchanged [default]
+    #[arg(long, overrides_with = "tolerate_errors", global = true)]
+    pub no_tolerate_errors: bool,
+
+    /// Apply a fix that changes the code beyond its layout. Can be specified multiple times.
+    #[arg(long, value_enum, value_name = "FIX", global = true)]
+    pub fix: Vec<Fix>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Fix {
+    /// Remove import items that are never referenced. Package entrypoints and files imported
+    /// by other files are skipped, as their imports may be re-exported.
+    UnusedImports,
 }
 
 #[derive(Args)]
@@ -0,0 +1,395 @@
+//! Discovery and loading of configuration files.
+//!
+//! Configuration is read from `typstyle.toml`, or from the `[tool.typstyle]` section of a
+//! Typst package manifest (`typst.toml`). Config files in parent directories are inherited:
+//! the options of the nearest file take precedence over those of its ancestors, until a file
+//! with `root = true` is reached. Options given on the command line take precedence over all.
+//!
+//! Besides formatting options, a config file may contain `exclude`, a list of gitignore-style
+//! patterns relative to its directory. Unlike options, exclusions of all applicable config files
+//! are combined.
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use anyhow::{bail, Context, Result};
+use ignore::{gitignore::Gitignore, WalkBuilder};
+use log::debug;
+use toml::Table;
+use typst_syntax::{ast, SyntaxNode};
+use typstyle_core::{Config, Typstyle};
+
+use crate::{
+    cli::{Fix, StyleArgs},
+    fs,
+};
+
+/// The name of the dedicated configuration file.
+pub const CONFIG_FILE_NAME: &str = "typstyle.toml";
+/// The name of the Typst package manifest, which may contain a `[tool.typstyle]` section.
+pub const MANIFEST_FILE_NAME: &str = "typst.toml";
+
+impl StyleArgs {
+    /// Overrides the options in `config` with those given on the command line.
+    pub fn apply_to(&self, config: &mut Config) {
+        if let Some(line_width) = self.line_width {
+            config.max_width = line_width;
+        }
+        if let Some(indent_width) = self.indent_width {
+            config.tab_spaces = indent_width;
+        }
+        if let Some(indent_style) = self.indent_style {
+            config.indent_style = indent_style;
+        }
+        if let Some(newline_style) = self.newline_style {
+            config.newline_style = newline_style;
+        }
+        if let Some(reorder) = flag(self.reorder_import_items, self.no_reorder_import_items) {
+            config.reorder_import_items = reorder;
+        }
+        if let Some(wrap_text) = self.wrap_text {
+            config.wrap_text = wrap_text;
+        }
+        if let Some(tolerate) = flag(self.tolerate_errors, self.no_tolerate_errors) {
+            config.tolerate_errors = tolerate;
+        }
+        if self.fix.contains(&Fix::UnusedImports) {
+            config.remove_unused_imports = true;
+        }
+    }
+}
+
+/// Gets the value of a pair of `--<flag>` and `--no-<flag>` options, or `None` if neither is
+/// given. The last one given wins, as they override each other.
+fn flag(on: bool, off: bool) -> Option<bool> {
+    match (on, off) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
+
+/// Resolves the formatter of each file according to the config files that apply to it.
+pub struct ConfigResolver<'a> {
+    args: &'a StyleArgs,
+    /// The config given by `--config`, if any.
+    explicit: Option<DirConfig>,
+    /// Merged config of visited directories.
+    dirs: HashMap<PathBuf, DirConfig>,
+    /// One formatter for each distinct config.
+    formatters: HashMap<Config, Typstyle>,
+    /// The entrypoint of each visited package manifest, if it declares one.
+    entrypoints: HashMap<PathBuf, Option<PathBuf>>,
+    /// The files imported by other files of each visited project.
+    imported: HashMap<PathBuf, HashSet<PathBuf>>,
+}
+
+/// The merged config that applies to a directory.
+#[derive(Clone, Default)]
+struct DirConfig {
+    /// Merged options. `None` if no config file applies.
+    table: Option<Table>,
+    /// Exclusion patterns of all applicable config files.
+    excludes: Vec<Rc<Gitignore>>,
+}
+
+/// A config file with the options it specifies.
+struct ConfigFile {
+    table: Table,
+    /// Whether to stop inheriting from parent directories.
+    is_root: bool,
+    /// Exclusion patterns, relative to the directory of the file.
+    exclude: Option<Rc<Gitignore>>,
+}
+
+impl<'a> ConfigResolver<'a> {
+    pub fn new(args: &'a StyleArgs) -> Result<Self> {
+        let explicit = match &args.config {
+            Some(path) => {
+                let file = load_config_file(&fs::normalize_path(path))?.with_context(|| {
+                    format!("no `[tool.typstyle]` section found in {}", path.display())
+                })?;
+                Some(DirConfig {
+                    table: Some(file.table),
+                    excludes: file.exclude.into_iter().collect(),
+                })
+            }
+            None => None,
+        };
+        Ok(Self {
+            args,
+            explicit,
+            dirs: Default::default(),
+            formatters: Default::default(),
+            entrypoints: Default::default(),
+            imported: Default::default(),
+        })
+    }
+
+    /// Resolves the formatter for the given file, or for stdin if `None`.
+    pub fn resolve(&mut self, input: Option<&Path>) -> Result<Typstyle> {
+        let config = self.resolve_config(input)?;
+        Ok(self
+            .formatters
+            .entry(config)
+            .or_insert_with_key(|config| Typstyle::new(config.clone()))
+            .clone())
+    }
+
+    /// Resolves the config for the given file, or for stdin if `None`.
+    pub fn resolve_config(&mut self, input: Option<&Path>) -> Result<Config> {
+        let mut config = match self.dir_config(input)?.table {
+            // Each file is validated when loaded, so merging them keeps the config valid.
+            Some(table) => table.try_into().context("invalid config")?,
+            None => Config::default(),
+        };
+        self.args.apply_to(&mut config);
+        if config.remove_unused_imports {
+            if let Some(path) = input {
+                if self.is_package_entrypoint(path)? {
+                    debug!(
+                        "Keeping unused imports of package entrypoint: {}",
+                        fs::relativize_path(path)
+                    );
+                    config.remove_unused_imports = false;
+                } else if self.is_imported(path) {
+                    debug!(
+                        "Keeping unused imports of file imported by others: {}",
+                        fs::relativize_path(path)
+                    );
+                    config.remove_unused_imports = false;
+                }
+            }
+        }
+        Ok(config)
+    }
+
+    /// Checks whether the file is the entrypoint of the nearest package, whose imports are
+    /// re-exported to the users of the package.
+    fn is_package_entrypoint(&mut self, path: &Path) -> Result<bool> {
+        let path = fs::normalize_path(path);
+        let Some(manifest) = path
+            .ancestors()
+            .skip(1)
+            .map(|dir| dir.join(MANIFEST_FILE_NAME))
+            .find(|manifest| manifest.is_file())
+        else {
+            return Ok(false);
+        };
+        let entrypoint = match self.entrypoints.get(&manifest) {
+            Some(entrypoint) => entrypoint.clone(),
+            None => {
+                let entrypoint = read_entrypoint(&manifest)?;
+                self.entrypoints.insert(manifest, entrypoint.clone());
+                entrypoint
+            }
+        };
+        Ok(entrypoint.is_some_and(|entrypoint| entrypoint == path))
+    }
+
+    /// Checks whether the file is imported by another file of its project, which may import
+    /// names from it that the file itself does not use.
+    fn is_imported(&mut self, path: &Path) -> bool {
+        let path = fs::normalize_path(path);
+        let root = project_root(&path);
+        self.imported
+            .entry(root)
+            .or_insert_with_key(|root| collect_imported_files(root))
+            .contains(&path)
+    }
+
+    /// Checks whether the file is excluded by the `exclude` option of config files.
+    pub fn is_excluded(&mut self, path: &Path) -> Result<bool> {
+        Ok(self
+            .dir_config(Some(path))?
+            .excludes
+            .iter()
+            .any(|exclude| fs::is_matched(exclude, path, false)))
+    }
+
+    /// Gets the merged config for the given file, or for stdin if `None`.
+    fn dir_config(&mut self, input: Option<&Path>) -> Result<DirConfig> {
+        if self.args.no_config {
+            Ok(DirConfig::default())
+        } else if let Some(explicit) = &self.explicit {
+            Ok(explicit.clone())
+        } else {
+            let dir = match input.and_then(Path::parent) {
+                Some(dir) => fs::normalize_path(dir),
+                None => std::env::current_dir().context("failed to get current directory")?,
+            };
+            self.discover(&dir)
+        }
+    }
+
+    /// Merges the config files from `dir` upwards.
+    fn discover(&mut self, dir: &Path) -> Result<DirConfig> {
+        if let Some(config) = self.dirs.get(dir) {
+            return Ok(config.clone());
+        }
+        let config = match read_config_in(dir)? {
+            Some(file) => {
+                let mut config = if file.is_root {
+                    DirConfig::default()
+                } else {
+                    self.discover_parent(dir)?
+                };
+                config
+                    .table
+                    .get_or_insert_with(Default::default)
+                    .extend(file.table);
+                config.excludes.extend(file.exclude);
+                config
+            }
+            None => self.discover_parent(dir)?,
+        };
+        self.dirs.insert(dir.to_path_buf(), config.clone());
+        Ok(config)
+    }
+
+    fn discover_parent(&mut self, dir: &Path) -> Result<DirConfig> {
+        match dir.parent() {
+            Some(parent) => self.discover(parent),
+            None => Ok(DirConfig::default()),
+        }
+    }
+}
+
+/// Reads the config file located directly in `dir`, if any.
+fn read_config_in(dir: &Path) -> Result<Option<ConfigFile>> {
+    for name in [CONFIG_FILE_NAME, MANIFEST_FILE_NAME] {
+        let path = dir.join(name);
+        if path.is_file() {
+            if let Some(file) = load_config_file(&path)? {
+                debug!("Using config file: {}", fs::relativize_path(&path));
+                return Ok(Some(file));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Finds the root of the project containing the file: the nearest directory with a package
+/// manifest or a git repository, or else the current directory.
+fn project_root(path: &Path) -> PathBuf {
+    let markers = [MANIFEST_FILE_NAME, ".git"];
+    path.ancestors()
+        .skip(1)
+        .find(|dir| markers.iter().any(|marker| dir.join(marker).exists()))
+        .map(Path::to_path_buf)
+        .or_else(|| {
+            std::env::current_dir()
+                .ok()
+                .filter(|cwd| path.starts_with(cwd))
+        })
+        .unwrap_or_else(|| path.parent().unwrap_or(Path::new("")).to_path_buf())
+}
+
+/// Collects the files imported by `#import "<path>"` in the Typst files under `root`.
+/// Absolute import paths are resolved against `root`, as Typst does for the project root.
+fn collect_imported_files(root: &Path) -> HashSet<PathBuf> {
+    fn collect(node: &SyntaxNode, out: &mut Vec<String>) {
+        if let Some(import) = node.cast::<ast::ModuleImport>() {
+            if let ast::Expr::Str(source) = import.source() {
+                out.push(source.get().to_string());
+            }
+        }
+        for child in node.children() {
+            collect(child, out);
+        }
+    }
+
+    let mut imported = HashSet::new();
+    let walker = WalkBuilder::new(root)
+        .hidden(true)
+        .require_git(false)
+        .build();
+    for entry in walker.filter_map(Result::ok) {
+        let path = entry.path();
+        if !entry.file_type().is_some_and(|ty| ty.is_file())
+            || path.extension() != Some("typ".as_ref())
+        {
+            continue;
+        }
+        let Ok(text) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        let mut sources = Vec::new();
+        collect(&typst_syntax::parse(&text), &mut sources);
+        let dir = path.parent().unwrap_or(root);
+        for source in sources {
+            // Packages are not part of the project.
+            if source.starts_with('@') {
+                continue;
+            }
+            let target = match source.strip_prefix('/') {
+                Some(source) => root.join(source),
+                None => dir.join(source),
+            };
+            imported.insert(fs::normalize_path(target));
+        }
+    }
+    imported
+}
+
+/// Reads the path of the entrypoint declared in a package manifest, if any.
+fn read_entrypoint(manifest: &Path) -> Result<Option<PathBuf>> {
+    let content = std::fs::read_to_string(manifest)
+        .with_context(|| format!("failed to read manifest {}", manifest.display()))?;
+    let table = toml::from_str::<Table>(&content)
+        .with_context(|| format!("failed to parse manifest {}", manifest.display()))?;
+    let entrypoint = table
+        .get("package")
+        .and_then(|package| package.get("entrypoint")?.as_str());
+    let root = manifest.parent().unwrap_or(Path::new(""));
+    Ok(entrypoint.map(|entrypoint| fs::normalize_path(root.join(entrypoint))))
+}
+
+/// Loads a config file. Returns `None` if it is a manifest without a `[tool.typstyle]` section.
+fn load_config_file(path: &Path) -> Result<Option<ConfigFile>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read config file {}", path.display()))?;
+    let mut table = toml::from_str::<Table>(&content)
+        .with_context(|| format!("failed to parse config file {}", path.display()))?;
+    if path.file_name() == Some(MANIFEST_FILE_NAME.as_ref()) {
+        match table
+            .remove("tool")
+            .and_then(|mut tool| tool.as_table_mut()?.remove("typstyle"))
+        {
+            Some(toml::Value::Table(section)) => table = section,
+            _ => return Ok(None),
+        }
+    }
+    let is_root = match table.remove("root") {
+        Some(toml::Value::Boolean(is_root)) => is_root,
+        Some(_) => bail!("`root` must be a boolean in {}", path.display()),
+        None => false,
+    };
+    let exclude = match table.remove("exclude") {
+        Some(exclude) => {
+            let patterns = exclude.try_into::<Vec<String>>().with_context(|| {
+                format!(
+                    "`exclude` must be an array of strings in {}",
+                    path.display()
+                )
+            })?;
+            let root = path.parent().unwrap_or(Path::new(""));
+            let matcher = fs::build_matcher(root, &patterns)
+                .with_context(|| format!("invalid `exclude` in {}", path.display()))?;
+            Some(Rc::new(matcher))
+        }
+        None => None,
+    };
+    // Validate the options early, so that errors are reported with the file path.
+    table
+        .clone()
+        .try_into::<Config>()
+        .with_context(|| format!("invalid config in {}", path.display()))?;
+    Ok(Some(ConfigFile {
+        table,
+        is_root,
+        exclude,
+    }))
+}
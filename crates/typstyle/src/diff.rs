@@ -0,0 +1,138 @@
+use std::{fmt::Write, ops::Range};
+
+use colored::{ColoredString, Colorize};
+use typstyle_core::TextEdit;
+
+/// The number of unchanged lines shown around each change.
+const CONTEXT: usize = 3;
+
+/// Renders a colored unified diff of the original contents and the edits that format it.
+///
+/// The edits must be sorted and must not overlap, as returned by [`TextEdit::diff`].
+pub fn unified_diff(name: &str, original: &str, edits: &[TextEdit]) -> String {
+    let lines = original.split_inclusive('\n').collect::<Vec<_>>();
+    let changes = line_changes(original, edits);
+
+    let mut out = String::new();
+    writeln!(out, "{}", format!("--- {name}").bold()).unwrap();
+    writeln!(out, "{}", format!("+++ {name}").bold()).unwrap();
+    let mut offset = 0; // The difference of line numbers before the current hunk.
+    let mut rest = &changes[..];
+    while let Some(first) = rest.first() {
+        // Changes close enough to share their context are shown in one hunk.
+        let count = 1 + rest
+            .windows(2)
+            .take_while(|pair| pair[1].0.start - pair[0].0.end <= 2 * CONTEXT)
+            .count();
+        let (hunk, next) = rest.split_at(count);
+        rest = next;
+
+        let old_start = first.0.start.saturating_sub(CONTEXT);
+        let old_end = (hunk[count - 1].0.end + CONTEXT).min(lines.len());
+        let new_len = hunk.iter().fold(old_end - old_start, |len, (old, text)| {
+            len - old.len() + text.split_inclusive('\n').count()
+        });
+        let new_start = old_start.checked_add_signed(offset).unwrap();
+        let header = format!(
+            "@@ -{} +{} @@",
+            hunk_range(old_start, old_end - old_start),
+            hunk_range(new_start, new_len)
+        );
+        writeln!(out, "{}", header.cyan()).unwrap();
+
+        let mut line = old_start;
+        for (old, text) in hunk {
+            write_lines(&mut out, &lines[line..old.start], ' ', |s| s.normal());
+            write_lines(&mut out, &lines[old.clone()], '-', |s| s.red());
+            let new = text.split_inclusive('\n').collect::<Vec<_>>();
+            write_lines(&mut out, &new, '+', |s| s.green());
+            offset += new.len() as isize - old.len() as isize;
+            line = old.end;
+        }
+        write_lines(&mut out, &lines[line..old_end], ' ', |s| s.normal());
+    }
+    out
+}
+
+/// Expands the edits to replacements of whole lines.
+///
+/// Each change is given as the 0-based range of original lines and the text replacing them.
+/// Edits in the same or adjacent lines are merged into one change.
+fn line_changes(original: &str, edits: &[TextEdit]) -> Vec<(Range<usize>, String)> {
+    let line_of = |offset: usize| original[..offset].matches('\n').count();
+    let is_line_start = |offset: usize| offset == 0 || original.as_bytes()[offset - 1] == b'\n';
+
+    let mut changes: Vec<(Range<usize>, String)> = Vec::new();
+    let mut edits = edits.iter().peekable();
+    while let Some(edit) = edits.next() {
+        let line_start = original[..edit.range.start]
+            .rfind('\n')
+            .map_or(0, |i| i + 1);
+        let mut text = original[line_start..edit.range.start].to_string();
+        text.push_str(&edit.replacement);
+        let mut pos = edit.range.end;
+        // Complete the last line, which may be joined with the following ones by removed line
+        // breaks, taking in the edits on the way.
+        while !(is_line_start(pos) && (text.is_empty() || text.ends_with('\n'))) {
+            let line_end = original[pos..]
+                .find('\n')
+                .map_or(original.len(), |i| pos + i + 1);
+            if let Some(next) = edits
+                .next_if(|next| next.range.start < line_end || next.range.start == original.len())
+            {
+                text.push_str(&original[pos..next.range.start]);
+                text.push_str(&next.replacement);
+                pos = next.range.end;
+            } else if pos == original.len() {
+                break;
+            } else {
+                text.push_str(&original[pos..line_end]);
+                pos = line_end;
+            }
+        }
+        let end = if pos == original.len() && !is_line_start(pos) {
+            line_of(pos) + 1
+        } else {
+            line_of(pos)
+        };
+        let start = line_of(line_start);
+        match changes.last_mut() {
+            // Adjacent changes are shown as one block of removed and added lines.
+            Some((old, prev)) if old.end == start => {
+                old.end = end;
+                prev.push_str(&text);
+            }
+            _ => changes.push((start..end, text)),
+        }
+    }
+    changes
+}
+
+/// Formats a range of lines in a hunk header, given its 0-based start and length.
+fn hunk_range(start: usize, len: usize) -> String {
+    match len {
+        0 => format!("{start},0"),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{len}", start + 1),
+    }
+}
+
+fn write_lines(
+    out: &mut String,
+    lines: &[&str],
+    tag: char,
+    color: impl Fn(String) -> ColoredString,
+) {
+    for line in lines {
+        let content = line.strip_suffix('\n');
+        writeln!(
+            out,
+            "{}",
+            color(format!("{tag}{}", content.unwrap_or(line)))
+        )
+        .unwrap();
+        if content.is_none() {
+            writeln!(out, "\\ No newline at end of file").unwrap();
+        }
+    }
+}
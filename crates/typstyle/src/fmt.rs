@@ -3,58 +3,73 @@
 ///
 /// Adapted from: https://github.com/astral-sh/ruff/blob/main/crates/ruff_linter/src/fs.rs
 use std::{
+    fmt::Write,
     io::Read,
+    num::NonZeroUsize,
+    ops::RangeInclusive,
     path::{Path, PathBuf},
     time::Instant,
 };
 
 use anyhow::{bail, Context, Result};
+use ignore::WalkBuilder;
 use itertools::Itertools;
 use log::{debug, error, info, warn};
+use rayon::prelude::*;
 use typst_syntax::Source;
-use typstyle_core::{Config, Typstyle};
-use walkdir::{DirEntry, WalkDir};
+use typstyle_core::{Error, SyntaxDiagnostic, TextEdit, Typstyle};
 
 use crate::{
-    cli::{CliArguments, DebugArgs, StyleArgs},
-    fs, ExitStatus,
+    cli::{CliArguments, DebugArgs, OutputFormat},
+    config::ConfigResolver,
+    diff, fs,
+    git::ChangedLines,
+    report::{FileStatus, Report},
+    ExitStatus,
 };
 
+/// The name of typstyle-specific ignore files, which follow the syntax of `.gitignore`.
+const IGNORE_FILE_NAME: &str = ".typstyleignore";
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum FormatMode {
     /// Write the formatted contents back to the file.
     Write,
     /// Check if the file is formatted, but do not write the formatted contents back.
     Check,
+    /// Like `Check`, but also print the diff between the original and formatted contents.
+    Diff,
 }
 
 impl FormatMode {
     pub(crate) fn from_cli(cli: &CliArguments) -> Self {
-        if cli.check {
+        if cli.diff {
+            FormatMode::Diff
+        } else if cli.check {
             FormatMode::Check
         } else {
             FormatMode::Write
         }
     }
-}
 
-impl StyleArgs {
-    pub fn to_config(&self) -> Config {
-        Config {
-            max_width: self.line_width,
-            tab_spaces: self.indent_width,
-            reorder_import_items: !self.no_reorder_import_items,
-            wrap_text: self.wrap_text,
-            ..Default::default()
-        }
+    /// Whether the formatted contents are only checked, but not written.
+    pub(crate) fn is_check(self) -> bool {
+        matches!(self, FormatMode::Check | FormatMode::Diff)
     }
 }
 
 pub fn format_stdin(args: &CliArguments) -> Result<ExitStatus> {
-    let typstyle = Typstyle::new(args.style.to_config());
+    let typstyle = ConfigResolver::new(&args.style)?.resolve(None)?;
 
-    format_one(None, &typstyle, args).map(|res| match res {
-        FormatResult::Formatted(_) if args.check => ExitStatus::Failure,
+    let mode = FormatMode::from_cli(args);
+    let mut report = new_report(args);
+    let lines = (!args.files.lines.is_empty()).then_some(args.files.lines.as_slice());
+    let res = format_one(None, &typstyle, lines, &args.debug)
+        .and_then(|output| emit_one(None, output, args, report.as_mut()))?;
+    print_report(report, args);
+
+    Ok(match res {
+        FormatResult::Formatted(..) if mode.is_check() => ExitStatus::Failure,
         _ => ExitStatus::Success,
     })
 }
@@ -69,29 +84,60 @@ pub fn format(args: &CliArguments) -> Result<ExitStatus> {
     let mut summary = Summary::default();
 
     let mode = FormatMode::from_cli(args);
-    let paths = resolve_typst_files(&args.input);
+    let mut resolver = ConfigResolver::new(&args.style)?;
+    let paths = resolve_typst_files(args, &mut resolver)?;
     if paths.is_empty() {
         warn!("No Typst files found under the given path(s).");
         return Ok(ExitStatus::Success);
     }
 
-    let typstyle = Typstyle::new(args.style.to_config());
+    let typstyles = paths
+        .iter()
+        .map(|file| resolver.resolve(Some(file)))
+        .collect::<Result<Vec<_>>>()?;
+    let changed = (args.files.changed_since.as_deref())
+        .map(ChangedLines::since)
+        .transpose()?;
+    let lines = paths
+        .iter()
+        .map(|file| match &changed {
+            Some(changed) => Some(changed.get(file)),
+            None => (!args.files.lines.is_empty()).then_some(args.files.lines.as_slice()),
+        })
+        .collect_vec();
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(args.jobs.map_or(0, NonZeroUsize::get))
+        .build()
+        .context("failed to create the thread pool")?;
 
+    let mut report = new_report(args);
     let start_time = Instant::now();
-    for file in paths {
-        let res = format_one(Some(&file), &typstyle, args).unwrap_or_else(|e| {
-            error!("{e}");
-            summary.error_count += 1;
-            FormatResult::Erroneous
-        });
+    // Files are formatted in parallel, but the results are emitted in order.
+    let outputs: Vec<_> = pool.install(|| {
+        paths
+            .par_iter()
+            .zip(&typstyles)
+            .zip(&lines)
+            .map(|((file, typstyle), lines)| format_one(Some(file), typstyle, *lines, &args.debug))
+            .collect()
+    });
+    for (file, output) in paths.iter().zip(outputs) {
+        let res = output
+            .and_then(|output| emit_one(Some(file), output, args, report.as_mut()))
+            .unwrap_or_else(|e| {
+                error!("{e}");
+                summary.error_count += 1;
+                FormatResult::Erroneous(vec![])
+            });
 
         // Check if the content is already well-formatted (unchanged)
         match res {
-            FormatResult::Formatted(_) => summary.format_count += 1,
+            FormatResult::Formatted(..) => summary.format_count += 1,
             _ => summary.unchanged_count += 1,
         }
     }
     let duration = start_time.elapsed();
+    print_report(report, args);
 
     fn num_files(num: usize) -> String {
         if num > 1 {
@@ -108,7 +154,7 @@ pub fn format(args: &CliArguments) -> Result<ExitStatus> {
             summary.unchanged_count,
             duration
         ),
-        FormatMode::Check => debug!(
+        FormatMode::Check | FormatMode::Diff => debug!(
             "{} would be reformatted ({} already formatted), checked in {:?}",
             num_files(summary.format_count),
             summary.unchanged_count,
@@ -124,103 +170,267 @@ pub fn format(args: &CliArguments) -> Result<ExitStatus> {
     }
 
     Ok(match mode {
-        FormatMode::Check if summary.format_count > 0 => ExitStatus::Failure,
+        _ if mode.is_check() && summary.format_count > 0 => ExitStatus::Failure,
         _ => ExitStatus::Success,
     })
 }
 
-/// Formats a single `.typ` file or input from stdin.
+/// Formats a single `.typ` file or input from stdin, without emitting the result.
 ///
-/// This function formats the file provided as an argument, or reads from stdin if no file is given.
-/// If in-place formatting is requested, it overwrites the file with the formatted content.
+/// This function can be called from multiple threads, as all output is buffered
+/// in the returned [`FormatOutput`].
 ///
 /// # Parameters
 /// - `input`: An optional path to a `.typ` file to be formatted. If `None`, input is read from stdin.
-/// - `args`: CLI arguments.
+/// - `typstyle`: The formatter to use.
+/// - `lines`: If given, only these 1-based inclusive line ranges are formatted.
+/// - `args`: Debug options of the CLI.
 ///
 /// # Returns
-/// - `Ok(FormatStatus::Changed)` if the file was reformatted.
-/// - `Ok(FormatStatus::Unchanged)` if the file was unchanged or contained errors.
-/// - `Err` if reading from or writing to the file fails.
+/// - `Ok(FormatOutput)` with the original content and the formatting result.
+/// - `Err` if reading from the file fails.
 fn format_one(
     input: Option<&PathBuf>,
     typstyle: &Typstyle,
+    lines: Option<&[RangeInclusive<usize>]>,
+    args: &DebugArgs,
+) -> Result<FormatOutput> {
+    let unformatted = get_input(input)?;
+    let mut debug = String::new();
+    let mut diagnostics = vec![];
+    let result = format_debug(
+        &unformatted,
+        typstyle,
+        lines,
+        args,
+        &mut debug,
+        &mut diagnostics,
+    );
+    Ok(FormatOutput {
+        unformatted,
+        result,
+        debug,
+        diagnostics,
+    })
+}
+
+/// Emits the result of formatting a single `.typ` file or input from stdin.
+///
+/// If in-place formatting is requested, it overwrites the file with the formatted content.
+/// Otherwise, the result is printed to stdout or checked, according to the mode.
+///
+/// # Parameters
+/// - `input`: An optional path to the formatted `.typ` file. If `None`, input was read from stdin.
+/// - `output`: The output of [`format_one`].
+/// - `args`: CLI arguments.
+/// - `report`: The report to record the result in, if a structured report is requested.
+///
+/// # Returns
+/// - `Ok(FormatResult::Formatted)` if the file was reformatted.
+/// - `Ok(FormatResult::Unchanged)` if the file was unchanged.
+/// - `Ok(FormatResult::Erroneous)` if the file contained errors.
+/// - `Err` if writing to the file fails.
+fn emit_one(
+    input: Option<&PathBuf>,
+    output: FormatOutput,
     args: &CliArguments,
+    report: Option<&mut Report>,
 ) -> Result<FormatResult> {
-    let use_stdout = !args.inplace && !args.check;
-    let unformatted = get_input(input)?;
+    let FormatOutput {
+        unformatted,
+        result: res,
+        debug,
+        diagnostics,
+    } = output;
+    let mode = FormatMode::from_cli(args);
+    let use_stdout = !args.inplace && !mode.is_check();
 
-    let res = format_debug(&unformatted, typstyle, &args.debug);
+    print!("{debug}");
+    if !diagnostics.is_empty() {
+        let name = input.map_or_else(|| "stdin".to_string(), fs::relativize_path);
+        for (line, message) in diagnostics {
+            warn!("{name}:{line}: {message}");
+        }
+    }
     match &res {
-        FormatResult::Formatted(res) => {
-            if args.inplace {
+        FormatResult::Formatted(res, edits) => match mode {
+            FormatMode::Write if args.inplace => {
                 // We have already validated that the input is Some.
                 write_back(input.unwrap(), res)?;
-            } else if args.check {
-                if let Some(path) = input {
+            }
+            FormatMode::Write => print!("{res}"),
+            FormatMode::Check => {
+                // Files to reformat are listed in the report instead, if requested.
+                if let (Some(path), None) = (input, &report) {
                     info!("Would reformat: {}", fs::relativize_path(path));
                 }
-            } else {
-                print!("{res}");
             }
-        }
+            FormatMode::Diff => {
+                let name = input.map_or_else(|| "stdin".to_string(), fs::relativize_path);
+                anstream::print!("{}", diff::unified_diff(&name, &unformatted, edits));
+            }
+        },
         FormatResult::Unchanged => {
             if use_stdout {
                 print!("{unformatted}");
             }
         }
-        FormatResult::Erroneous => {
+        FormatResult::Erroneous(errors) => {
             if use_stdout {
                 print!("{unformatted}"); // still prints the original content to enable piping
             }
-            if let Some(path) = input {
-                warn!(
-                    "Failed to parse {}. The source is erroneous.",
-                    fs::relativize_path(path)
-                );
-            } else {
-                warn!("Failed to parse stdin. The source is erroneous.");
+            let name = input.map_or_else(|| "stdin".to_string(), fs::relativize_path);
+            let mut details = String::new();
+            for error in errors {
+                // Hints on the following lines are indented under the error.
+                let error = error.to_string().replace('\n', "\n  ");
+                write!(details, "\n  {name}:{error}").unwrap();
             }
+            warn!("Failed to parse {name}. The source is erroneous.{details}");
+        }
+    }
+    if let Some(report) = report {
+        let name = input.map_or_else(|| "stdin".to_string(), fs::relativize_path);
+        match &res {
+            FormatResult::Formatted(res, _) => {
+                report.add(name, FileStatus::Changed, &unformatted, res)
+            }
+            FormatResult::Unchanged => report.add(name, FileStatus::Unchanged, &unformatted, ""),
+            FormatResult::Erroneous(_) => report.add(name, FileStatus::Erroneous, &unformatted, ""),
         }
     }
     Ok(res)
 }
 
+/// Creates a report to collect the results in, if a structured report is requested.
+fn new_report(args: &CliArguments) -> Option<Report> {
+    (args.output_format != OutputFormat::Text).then(Report::default)
+}
+
+fn print_report(report: Option<Report>, args: &CliArguments) {
+    if let Some(output) = report.and_then(|report| report.render(args.output_format)) {
+        print!("{output}");
+    }
+}
+
+/// The buffered output of [`format_one`].
+struct FormatOutput {
+    unformatted: String,
+    result: FormatResult,
+    /// The output of debug options, printed before the result.
+    debug: String,
+    /// Problems with `@typstyle` directives, as 1-based line numbers and messages.
+    diagnostics: Vec<(usize, String)>,
+}
+
 enum FormatResult {
-    Formatted(String),
+    /// The formatted content, and the edits that turn the original content into it.
+    Formatted(String, Vec<TextEdit>),
     Unchanged,
-    Erroneous,
+    /// The source has syntax errors, which are listed if known.
+    Erroneous(Vec<SyntaxDiagnostic>),
 }
 
-fn format_debug(content: &str, typstyle: &Typstyle, args: &DebugArgs) -> FormatResult {
+fn format_debug(
+    content: &str,
+    typstyle: &Typstyle,
+    lines: Option<&[RangeInclusive<usize>]>,
+    args: &DebugArgs,
+    out: &mut String,
+    diagnostics: &mut Vec<(usize, String)>,
+) -> FormatResult {
+    if lines.is_some_and(|lines| lines.is_empty()) {
+        return FormatResult::Unchanged;
+    }
     let source = Source::detached(content);
     let root = source.root();
     if args.ast {
-        println!("{root:#?}");
+        writeln!(out, "{root:#?}").unwrap();
     }
 
     let start_time = Instant::now();
-    let f = typstyle.format_source(source);
-    if args.pretty_doc {
-        match f.render_ir() {
-            Ok(ir) => println!("{ir}"),
-            Err(e) => error!("Failed to render IR: {e}"),
+    let f = typstyle.format_source(source.clone());
+    // Directives are checked in the whole file, even if only some lines are formatted.
+    diagnostics.extend(f.diagnostics().iter().filter_map(|diag| {
+        let line = source.byte_to_line(source.range(diag.span)?.start)?;
+        Some((line + 1, diag.message.clone()))
+    }));
+    let edits = match lines {
+        Some(lines) => format_lines(&source, typstyle, lines),
+        None => {
+            if args.pretty_doc {
+                match f.render_ir() {
+                    Ok(ir) => writeln!(out, "{ir}").unwrap(),
+                    Err(e) => error!("Failed to render IR: {e}"),
+                }
+            }
+            f.render_edits()
         }
-    }
-    let Ok(res) = f.render() else {
-        return FormatResult::Erroneous;
+    };
+    let edits = match edits {
+        Ok(edits) => edits,
+        Err(Error::SyntaxError(errors)) => return FormatResult::Erroneous(errors),
+        Err(Error::RenderError) => return FormatResult::Erroneous(vec![]),
     };
 
     if args.timing {
-        println!("Formatting completed in {:?}", start_time.elapsed());
+        writeln!(out, "Formatting completed in {:?}", start_time.elapsed()).unwrap();
     }
 
-    // Compare `res` with `content` to perform CI checks
-    if res != content {
-        FormatResult::Formatted(res)
-    } else {
-        FormatResult::Unchanged
+    if edits.is_empty() {
+        return FormatResult::Unchanged;
+    }
+    // Apply from the last edit, so that the offsets of preceding edits remain valid.
+    let mut res = content.to_string();
+    for edit in edits.iter().rev() {
+        res.replace_range(edit.range.clone(), &edit.replacement);
     }
+    FormatResult::Formatted(res, edits)
+}
+
+/// Formats only the given 1-based inclusive line ranges of the source, returning the edits.
+///
+/// Each range is expanded to the smallest node covering it, so the formatted region may be
+/// larger than the given lines. Lines that only contain whitespace are skipped.
+fn format_lines(
+    source: &Source,
+    typstyle: &Typstyle,
+    lines: &[RangeInclusive<usize>],
+) -> Result<Vec<TextEdit>, Error> {
+    let text = source.text();
+    let line_starts = std::iter::once(0)
+        .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+        .collect_vec();
+    let ranges = lines
+        .iter()
+        .filter(|range| *range.start() <= line_starts.len())
+        .map(|range| {
+            let start = line_starts[range.start() - 1];
+            let end = line_starts
+                .get(*range.end())
+                .map_or(text.len(), |next| next - 1);
+            let start = end - text[start..end].trim_start().len();
+            // Skip the hash of embedded code, which is not part of the expression.
+            let start = start + usize::from(text[start..end].starts_with('#'));
+            start..end
+        })
+        .filter(|range| !text[range.clone()].trim().is_empty());
+    let edits = typstyle.format_source_ranges(source, ranges)?;
+
+    // Only replace the characters that differ, as for the whole source.
+    let edits = edits
+        .into_iter()
+        .flat_map(|edit| {
+            TextEdit::diff(&text[edit.range.clone()], &edit.replacement)
+                .into_iter()
+                .map(move |mut diff| {
+                    diff.range =
+                        diff.range.start + edit.range.start..diff.range.end + edit.range.start;
+                    diff
+                })
+        })
+        .collect();
+    Ok(edits)
 }
 
 fn get_input(input: Option<&PathBuf>) -> Result<String> {
@@ -242,27 +452,39 @@ fn write_back(path: &Path, content: &str) -> Result<()> {
         .with_context(|| format!("failed to write to the file {}", path.display()))
 }
 
-fn resolve_typst_files(input: &[PathBuf]) -> Vec<PathBuf> {
-    fn is_hidden(entry: &DirEntry) -> bool {
-        entry
-            .file_name()
-            .to_str()
-            .is_some_and(|s| s.starts_with('.'))
-    }
+/// Collects the files to format. Directories are searched recursively for `.typ` files,
+/// skipping hidden entries, those ignored by ignore files, and those excluded by the CLI or
+/// config files. Files given explicitly are always included.
+fn resolve_typst_files(args: &CliArguments, resolver: &mut ConfigResolver) -> Result<Vec<PathBuf>> {
+    let cwd = std::env::current_dir().context("failed to get current directory")?;
+    let exclude = fs::build_matcher(&cwd, &args.files.exclude).context("invalid `--exclude`")?;
+    let include = fs::build_matcher(&cwd, &args.files.include).context("invalid `--include`")?;
 
     let mut files = Vec::new();
     let mut has_dir = false;
-    for path in input.iter().map(fs::normalize_path).unique() {
+    for path in args.input.iter().map(fs::normalize_path).unique() {
         if path.is_dir() {
             has_dir = true;
-            let entries = WalkDir::new(path)
-                .into_iter()
-                .filter_entry(|e| !is_hidden(e))
-                .filter_map(Result::ok);
+            let mut walker = WalkBuilder::new(path);
+            walker
+                .standard_filters(!args.files.no_ignore)
+                .hidden(true)
+                .require_git(false);
+            if !args.files.no_ignore {
+                walker.add_custom_ignore_filename(IGNORE_FILE_NAME);
+            }
+            let entries = walker.build().filter_map(Result::ok);
             for entry in entries {
-                if entry.file_type().is_file() && entry.path().extension() == Some("typ".as_ref()) {
-                    files.push(entry.into_path());
+                let path = entry.path();
+                if !entry.file_type().is_some_and(|ty| ty.is_file())
+                    || path.extension() != Some("typ".as_ref())
+                    || fs::is_matched(&exclude, path, false)
+                    || (!include.is_empty() && !fs::is_matched(&include, path, false))
+                    || resolver.is_excluded(path)?
+                {
+                    continue;
                 }
+                files.push(entry.into_path());
             }
         } else {
             files.push(path.clone());
@@ -271,5 +493,5 @@ fn resolve_typst_files(input: &[PathBuf]) -> Vec<PathBuf> {
     if has_dir {
         files.sort_unstable();
     }
-    files
+    Ok(files)
 }
@@ -2,6 +2,11 @@
 
 use std::path::{Path, PathBuf};
 
+use anyhow::{Context, Result};
+use ignore::{
+    gitignore::{Gitignore, GitignoreBuilder},
+    Match,
+};
 use path_absolutize::Absolutize;
 
 /// Convert any path to an absolute path (based on the current working directory).
@@ -20,3 +25,31 @@ pub fn relativize_path<P: AsRef<Path>>(path: P) -> String {
         .display()
         .to_string()
 }
+
+/// Build a matcher from gitignore-style patterns, which are relative to `root`.
+pub fn build_matcher<P: AsRef<Path>>(root: P, patterns: &[String]) -> Result<Gitignore> {
+    let mut builder = GitignoreBuilder::new(root);
+    for pattern in patterns {
+        builder
+            .add_line(None, pattern)
+            .with_context(|| format!("invalid pattern `{pattern}`"))?;
+    }
+    Ok(builder.build()?)
+}
+
+/// Check whether the path or any of its parents under the root matches the patterns.
+pub fn is_matched(matcher: &Gitignore, path: &Path, is_dir: bool) -> bool {
+    let root = matcher.path();
+    for (i, path) in path
+        .ancestors()
+        .take_while(|p| p.starts_with(root) && *p != root)
+        .enumerate()
+    {
+        match matcher.matched(path, is_dir || i > 0) {
+            Match::None => {}
+            Match::Ignore(_) => return true,
+            Match::Whitelist(_) => return false,
+        }
+    }
+    false
+}
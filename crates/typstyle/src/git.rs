@@ -0,0 +1,145 @@
+//! Collects the lines changed relative to a git revision.
+use std::{
+    collections::HashMap,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{bail, Context, Result};
+
+/// The changed lines of each file, as 1-based inclusive ranges.
+pub struct ChangedLines {
+    files: HashMap<PathBuf, Vec<RangeInclusive<usize>>>,
+}
+
+impl ChangedLines {
+    /// Collects the lines changed since `rev`, including uncommitted changes in the working tree.
+    /// Untracked files are considered changed entirely.
+    pub fn since(rev: &str) -> Result<Self> {
+        let root = git(None, &["rev-parse", "--show-toplevel"])?;
+        let root = canonicalize(Path::new(root.trim()));
+
+        let diff = git(
+            Some(&root),
+            &[
+                "diff",
+                "--unified=0",
+                "--no-color",
+                "--no-ext-diff",
+                "--src-prefix=a/",
+                "--dst-prefix=b/",
+                rev,
+                "--",
+            ],
+        )?;
+        let mut files: HashMap<_, Vec<_>> = HashMap::new();
+        let mut current = None;
+        for line in diff.lines() {
+            if let Some(path) = line.strip_prefix("+++ ") {
+                current = parse_diff_path(path)
+                    .and_then(|path| Some(root.join(path.strip_prefix("b/")?)));
+            } else if let (Some(hunk), Some(path)) = (line.strip_prefix("@@ "), &current) {
+                if let Some(range) = parse_hunk_header(hunk) {
+                    files.entry(path.clone()).or_default().push(range);
+                }
+            }
+        }
+
+        let untracked = git(
+            Some(&root),
+            &[
+                "ls-files",
+                "--others",
+                "--exclude-standard",
+                "--full-name",
+                "-z",
+            ],
+        )?;
+        for path in untracked.split_terminator('\0') {
+            files.insert(root.join(path), vec![1..=usize::MAX]);
+        }
+
+        Ok(Self { files })
+    }
+
+    /// Gets the changed lines of the file, which are empty if the file is unchanged.
+    pub fn get(&self, path: &Path) -> &[RangeInclusive<usize>] {
+        self.files
+            .get(&canonicalize(path))
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Parses the range of new lines from a hunk header like `-1,2 +3,4 @@`.
+/// Returns `None` if the hunk only deletes lines.
+fn parse_hunk_header(hunk: &str) -> Option<RangeInclusive<usize>> {
+    let new = hunk.split_whitespace().find_map(|s| s.strip_prefix('+'))?;
+    let (start, count) = match new.split_once(',') {
+        Some((start, count)) => (start.parse().ok()?, count.parse().ok()?),
+        None => (new.parse().ok()?, 1),
+    };
+    (count > 0).then(|| start..=start + count - 1)
+}
+
+/// Parses the path of a `---` or `+++` line in a diff.
+///
+/// Git appends a tab to paths containing spaces, and quotes paths with special characters in
+/// C style, such as `"b/a\"b.typ"`. Returns `None` for `/dev/null` or an invalid quoted path.
+fn parse_diff_path(path: &str) -> Option<String> {
+    let path = path.strip_suffix('\t').unwrap_or(path);
+    let Some(quoted) = path.strip_prefix('"') else {
+        return (path != "/dev/null").then(|| path.to_string());
+    };
+    let mut bytes = Vec::new();
+    let mut chars = quoted.strip_suffix('"')?.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        bytes.push(match chars.next()? {
+            'a' => b'\x07',
+            'b' => b'\x08',
+            'f' => b'\x0c',
+            'n' => b'\n',
+            'r' => b'\r',
+            't' => b'\t',
+            'v' => b'\x0b',
+            // Non-ASCII bytes are escaped in octal, unless `core.quotePath` is false.
+            digit @ '0'..='3' => {
+                let rest = [chars.next()?, chars.next()?];
+                let octal = [digit, rest[0], rest[1]].iter().collect::<String>();
+                u8::from_str_radix(&octal, 8).ok()?
+            }
+            c => u8::try_from(c).ok()?,
+        });
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn canonicalize(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Runs a git command and returns its stdout.
+fn git(dir: Option<&Path>, args: &[&str]) -> Result<String> {
+    let mut command = Command::new("git");
+    if let Some(dir) = dir {
+        command.current_dir(dir);
+    }
+    let output = command
+        .args(["-c", "core.quotePath=false"])
+        .args(args)
+        .output()
+        .context("failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    String::from_utf8(output.stdout).context("the output of git is not valid UTF-8")
+}
@@ -1,11 +1,18 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use colored::Colorize;
 use log::{Level, LevelFilter, Metadata, Record};
 
 pub struct SimpleLogger;
 
+/// Whether to print all logs to stderr, as stdout is used for other purposes.
+static STDERR_ONLY: AtomicBool = AtomicBool::new(false);
+
 impl log::Log for SimpleLogger {
     fn enabled(&self, metadata: &Metadata) -> bool {
+        // Ignore logs from dependencies.
         metadata.level() <= log::max_level()
+            && metadata.target().split("::").next() == Some(env!("CARGO_CRATE_NAME"))
     }
 
     fn log(&self, record: &Record) {
@@ -20,6 +27,9 @@ impl log::Log for SimpleLogger {
                     ":".bold(),
                     record.args()
                 ),
+                _ if STDERR_ONLY.load(Ordering::Relaxed) => {
+                    anstream::eprintln!("{}", record.args())
+                }
                 _ => anstream::println!("{}", record.args()),
             }
         }
@@ -33,3 +43,8 @@ pub fn init() {
         .map(|_| log::set_max_level(LevelFilter::Info))
         .expect("failed to init logging");
 }
+
+/// Prints all logs to stderr, including those that are printed to stdout by default.
+pub fn redirect_to_stderr() {
+    STDERR_ONLY.store(true, Ordering::Relaxed);
+}
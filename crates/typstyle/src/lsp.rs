@@ -0,0 +1,496 @@
+//! A language server that provides formatting over stdio.
+//!
+//! Documents are synchronized in full, and formatted with the config resolved for their paths,
+//! just like files given on the command line.
+use std::{collections::HashMap, ops::Range};
+
+use anyhow::{Context, Result};
+use log::{debug, warn};
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+        Notification as NotificationTrait,
+    },
+    request::{Formatting, OnTypeFormatting, RangeFormatting, Request as RequestTrait},
+    DocumentOnTypeFormattingOptions, OneOf, Position, ServerCapabilities, ServerInfo,
+    TextDocumentSyncCapability, TextDocumentSyncKind, TextEdit, Url,
+};
+use serde_json::json;
+use typst_syntax::Source;
+
+use crate::{cli::StyleArgs, config::ConfigResolver, logging, ExitStatus};
+
+/// Characters that trigger formatting of the node they close.
+const TRIGGER_CHARACTERS: [&str; 3] = ["}", "]", ")"];
+
+/// Runs the language server over stdio until the client exits.
+pub fn run(style: &StyleArgs) -> Result<ExitStatus> {
+    // Stdout is reserved for the protocol.
+    logging::redirect_to_stderr();
+
+    let (connection, io_threads) = Connection::stdio();
+    Server::new(style)?.serve(&connection)?;
+    drop(connection);
+    io_threads.join().context("failed to join the IO threads")?;
+    Ok(ExitStatus::Success)
+}
+
+struct Server<'a> {
+    resolver: ConfigResolver<'a>,
+    /// Contents of open documents.
+    documents: HashMap<Url, Source>,
+}
+
+impl<'a> Server<'a> {
+    fn new(style: &'a StyleArgs) -> Result<Self> {
+        Ok(Self {
+            resolver: ConfigResolver::new(style)?,
+            documents: HashMap::new(),
+        })
+    }
+
+    fn serve(&mut self, connection: &Connection) -> Result<()> {
+        let (id, _) = connection.initialize_start()?;
+        let capabilities = ServerCapabilities {
+            text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+            document_formatting_provider: Some(OneOf::Left(true)),
+            document_range_formatting_provider: Some(OneOf::Left(true)),
+            document_on_type_formatting_provider: Some(DocumentOnTypeFormattingOptions {
+                first_trigger_character: TRIGGER_CHARACTERS[0].to_string(),
+                more_trigger_character: Some(
+                    TRIGGER_CHARACTERS[1..]
+                        .iter()
+                        .map(|s| s.to_string())
+                        .collect(),
+                ),
+            }),
+            ..Default::default()
+        };
+        let server_info = ServerInfo {
+            name: "typstyle".to_string(),
+            version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        };
+        let result = json!({ "capabilities": capabilities, "serverInfo": server_info });
+        connection.initialize_finish(id, result)?;
+
+        for message in &connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = self.handle_request(request);
+                    connection.sender.send(response.into())?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification),
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&mut self, request: Request) -> Response {
+        let id = request.id.clone();
+        let result = match request.method.as_str() {
+            Formatting::METHOD => parse_params::<Formatting>(request).and_then(|params| {
+                self.format(&params.text_document.uri, |source| {
+                    Some(0..source.len_bytes())
+                })
+            }),
+            RangeFormatting::METHOD => {
+                parse_params::<RangeFormatting>(request).and_then(|params| {
+                    self.format(&params.text_document.uri, |source| {
+                        Some(
+                            to_offset(source, params.range.start)?
+                                ..to_offset(source, params.range.end)?,
+                        )
+                    })
+                })
+            }
+            OnTypeFormatting::METHOD => {
+                parse_params::<OnTypeFormatting>(request).and_then(|params| {
+                    let position = params.text_document_position;
+                    self.format(&position.text_document.uri, |source| {
+                        // Format the node closed by the typed character.
+                        let end = to_offset(source, position.position)?;
+                        let start = source.text()[..end].char_indices().next_back()?.0;
+                        Some(start..end)
+                    })
+                })
+            }
+            method => Err(ResponseError(
+                ErrorCode::MethodNotFound,
+                format!("unsupported request: {method}"),
+            )),
+        };
+        match result {
+            Ok(edits) => Response::new_ok(id, edits),
+            Err(ResponseError(code, message)) => Response::new_err(id, code as i32, message),
+        }
+    }
+
+    fn handle_notification(&mut self, notification: Notification) {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                if let Some(params) = parse_notification::<DidOpenTextDocument>(notification) {
+                    let document = params.text_document;
+                    self.documents
+                        .insert(document.uri, Source::detached(document.text));
+                }
+            }
+            DidChangeTextDocument::METHOD => {
+                if let Some(params) = parse_notification::<DidChangeTextDocument>(notification) {
+                    // With full synchronization, the last change contains the whole document.
+                    if let Some(change) = params.content_changes.into_iter().last() {
+                        self.documents
+                            .insert(params.text_document.uri, Source::detached(change.text));
+                    }
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                if let Some(params) = parse_notification::<DidCloseTextDocument>(notification) {
+                    self.documents.remove(&params.text_document.uri);
+                }
+            }
+            method => debug!("Ignored notification: {method}"),
+        }
+    }
+
+    /// Formats the node covering the range of the document given by `get_range`, or the whole
+    /// document if it is the full range. Returns `None` if the document has syntax errors.
+    fn format(
+        &mut self,
+        uri: &Url,
+        get_range: impl FnOnce(&Source) -> Option<Range<usize>>,
+    ) -> Result<Option<Vec<TextEdit>>, ResponseError> {
+        let source = self.documents.get(uri).ok_or_else(|| {
+            ResponseError(ErrorCode::InvalidParams, format!("unknown document: {uri}"))
+        })?;
+        let range = get_range(source).ok_or_else(|| {
+            ResponseError(ErrorCode::InvalidParams, "invalid position".to_string())
+        })?;
+
+        let path = uri.to_file_path().ok();
+        let typstyle = self
+            .resolver
+            .resolve(path.as_deref())
+            .map_err(|err| ResponseError(ErrorCode::RequestFailed, format!("{err:#}")))?;
+
+        let edits = if range == (0..source.len_bytes()) {
+            typstyle.format_source(source.clone()).render_edits()
+        } else {
+            typstyle.format_source_ranges(source, [range]).map(|edits| {
+                // Only replace the characters that differ.
+                edits
+                    .into_iter()
+                    .flat_map(|edit| {
+                        let original = &source.text()[edit.range.clone()];
+                        typstyle_core::TextEdit::diff(original, &edit.replacement)
+                            .into_iter()
+                            .map(move |mut diff| {
+                                diff.range = diff.range.start + edit.range.start
+                                    ..diff.range.end + edit.range.start;
+                                diff
+                            })
+                    })
+                    .collect()
+            })
+        };
+        match edits {
+            Ok(edits) => Ok(Some(
+                edits
+                    .into_iter()
+                    .map(|edit| TextEdit {
+                        range: lsp_types::Range {
+                            start: to_position(source, edit.range.start),
+                            end: to_position(source, edit.range.end),
+                        },
+                        new_text: edit.replacement,
+                    })
+                    .collect(),
+            )),
+            Err(err) => {
+                warn!("Failed to format {uri}: {err}");
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// An error to respond to a request with.
+struct ResponseError(ErrorCode, String);
+
+fn parse_params<R: RequestTrait>(request: Request) -> Result<R::Params, ResponseError> {
+    request
+        .extract::<R::Params>(R::METHOD)
+        .map(|(_, params): (RequestId, _)| params)
+        .map_err(|err| ResponseError(ErrorCode::InvalidParams, err.to_string()))
+}
+
+fn parse_notification<N: NotificationTrait>(notification: Notification) -> Option<N::Params> {
+    notification
+        .extract::<N::Params>(N::METHOD)
+        .inspect_err(|err| warn!("Invalid notification: {err}"))
+        .ok()
+}
+
+/// Converts an LSP position in UTF-16 code units to a byte offset.
+fn to_offset(source: &Source, position: Position) -> Option<usize> {
+    let line = position.line as usize;
+    if line == source.len_lines() {
+        return Some(source.len_bytes());
+    }
+    let line_range = source.line_to_range(line)?;
+    // Positions beyond the end of the line are clamped to its end.
+    let line_end = line_range.start
+        + source.text()[line_range.clone()]
+            .trim_end_matches(['\r', '\n'])
+            .len();
+    let utf16 = source.byte_to_utf16(line_range.start)? + position.character as usize;
+    Some(
+        source
+            .utf16_to_byte(utf16)
+            .map_or(line_end, |offset| offset.min(line_end)),
+    )
+}
+
+/// Converts a byte offset to an LSP position in UTF-16 code units.
+fn to_position(source: &Source, offset: usize) -> Position {
+    let line = source.byte_to_line(offset).unwrap_or(0);
+    let line_start = source.line_to_byte(line).unwrap_or(0);
+    let character =
+        source.byte_to_utf16(offset).unwrap_or(0) - source.byte_to_utf16(line_start).unwrap_or(0);
+    Position::new(line as u32, character as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, thread};
+
+    use clap::Parser;
+    use serde_json::Value;
+
+    use super::*;
+    use crate::cli::CliArguments;
+
+    /// A fake LSP client, which talks to the server over an in-memory connection.
+    struct Client {
+        connection: Connection,
+        next_id: i32,
+        /// The result of the `initialize` request.
+        initialized: Value,
+    }
+
+    impl Client {
+        fn request(&mut self, method: &str, params: Value) -> Value {
+            let response = self.request_raw(method, params);
+            assert!(response.error.is_none(), "{:?}", response.error);
+            response.result.unwrap_or(Value::Null)
+        }
+
+        fn request_raw(&mut self, method: &str, params: Value) -> Response {
+            self.next_id += 1;
+            let id = RequestId::from(self.next_id);
+            let request = Request::new(id.clone(), method.to_string(), params);
+            self.connection.sender.send(request.into()).unwrap();
+            loop {
+                if let Message::Response(response) = self.connection.receiver.recv().unwrap() {
+                    if response.id == id {
+                        return response;
+                    }
+                }
+            }
+        }
+
+        fn notify(&self, method: &str, params: Value) {
+            let notification = Notification::new(method.to_string(), params);
+            self.connection.sender.send(notification.into()).unwrap();
+        }
+
+        fn open(&self, uri: &Url, text: &str) {
+            self.notify(
+                DidOpenTextDocument::METHOD,
+                json!({
+                    "textDocument": { "uri": uri, "languageId": "typst", "version": 1, "text": text }
+                }),
+            );
+        }
+
+        fn format(&mut self, uri: &Url) -> Value {
+            self.request(
+                Formatting::METHOD,
+                json!({
+                    "textDocument": { "uri": uri },
+                    "options": { "tabSize": 2, "insertSpaces": true },
+                }),
+            )
+        }
+    }
+
+    /// Runs a server in a thread and `f` with a client connected to it, then shuts it down.
+    fn with_client(f: impl FnOnce(&mut Client)) {
+        let args = CliArguments::parse_from(["typstyle"]);
+        let (server_connection, connection) = Connection::memory();
+        thread::scope(|scope| {
+            let server = scope.spawn(|| Server::new(&args.style)?.serve(&server_connection));
+            let mut client = Client {
+                connection,
+                next_id: 0,
+                initialized: Value::Null,
+            };
+            client.initialized = client.request("initialize", json!({ "capabilities": {} }));
+            client.notify("initialized", json!({}));
+
+            f(&mut client);
+
+            client.request("shutdown", Value::Null);
+            client.notify("exit", Value::Null);
+            server.join().unwrap().unwrap();
+        });
+    }
+
+    fn file_uri(dir: &Path, name: &str) -> Url {
+        Url::from_file_path(dir.join(name)).unwrap()
+    }
+
+    fn edit(start: (u32, u32), end: (u32, u32), text: &str) -> Value {
+        json!({
+            "range": {
+                "start": { "line": start.0, "character": start.1 },
+                "end": { "line": end.0, "character": end.1 },
+            },
+            "newText": text,
+        })
+    }
+
+    #[test]
+    fn test_capabilities() {
+        with_client(|client| {
+            assert_eq!(client.initialized["serverInfo"]["name"], "typstyle");
+            assert_eq!(
+                client.initialized["capabilities"],
+                json!({
+                    "textDocumentSync": 1,
+                    "documentFormattingProvider": true,
+                    "documentRangeFormattingProvider": true,
+                    "documentOnTypeFormattingProvider": {
+                        "firstTriggerCharacter": "}",
+                        "moreTriggerCharacter": ["]", ")"],
+                    },
+                })
+            );
+        });
+    }
+
+    #[test]
+    fn test_formatting() {
+        let dir = tempfile::tempdir().unwrap();
+        let uri = file_uri(dir.path(), "a.typ");
+        with_client(|client| {
+            client.open(&uri, "#let  x  = (1+2)\n");
+            assert_eq!(
+                client.format(&uri),
+                json!([
+                    edit((0, 5), (0, 6), ""),
+                    edit((0, 7), (0, 8), ""),
+                    edit((0, 13), (0, 13), " "),
+                    edit((0, 14), (0, 14), " "),
+                ])
+            );
+
+            client.notify(
+                DidChangeTextDocument::METHOD,
+                json!({
+                    "textDocument": { "uri": uri, "version": 2 },
+                    "contentChanges": [{ "text": "#let x = (1 + 2)\n" }],
+                }),
+            );
+            assert_eq!(client.format(&uri), json!([]));
+        });
+    }
+
+    #[test]
+    fn test_formatting_with_config() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("typstyle.toml"), "tab_spaces = 4\n").unwrap();
+        let uri = file_uri(dir.path(), "a.typ");
+        with_client(|client| {
+            client.open(&uri, "#{\n1\n}\n");
+            assert_eq!(client.format(&uri), json!([edit((1, 0), (1, 0), "    ")]));
+        });
+    }
+
+    #[test]
+    fn test_range_formatting() {
+        let dir = tempfile::tempdir().unwrap();
+        let uri = file_uri(dir.path(), "a.typ");
+        with_client(|client| {
+            client.open(&uri, "#let a  =  1\n#let b  =  \"中文\"\n");
+            let result = client.request(
+                RangeFormatting::METHOD,
+                json!({
+                    "textDocument": { "uri": uri },
+                    "range": {
+                        "start": { "line": 1, "character": 1 },
+                        "end": { "line": 1, "character": 16 },
+                    },
+                    "options": { "tabSize": 2, "insertSpaces": true },
+                }),
+            );
+            assert_eq!(
+                result,
+                json!([edit((1, 7), (1, 8), ""), edit((1, 9), (1, 10), "")])
+            );
+        });
+    }
+
+    #[test]
+    fn test_on_type_formatting() {
+        let dir = tempfile::tempdir().unwrap();
+        let uri = file_uri(dir.path(), "a.typ");
+        with_client(|client| {
+            client.open(&uri, "#let  a  =  1\n#let f(x) = {\nx+1}\n");
+            let result = client.request(
+                OnTypeFormatting::METHOD,
+                json!({
+                    "textDocument": { "uri": uri },
+                    "position": { "line": 2, "character": 4 },
+                    "ch": "}",
+                    "options": { "tabSize": 2, "insertSpaces": true },
+                }),
+            );
+            assert_eq!(
+                result,
+                json!([
+                    edit((2, 0), (2, 0), "  "),
+                    edit((2, 1), (2, 1), " "),
+                    edit((2, 2), (2, 2), " "),
+                    edit((2, 3), (2, 3), "\n"),
+                ])
+            );
+        });
+    }
+
+    #[test]
+    fn test_erroneous() {
+        let dir = tempfile::tempdir().unwrap();
+        let uri = file_uri(dir.path(), "a.typ");
+        with_client(|client| {
+            client.open(&uri, "#let a = (\n");
+            assert_eq!(client.format(&uri), Value::Null);
+
+            let response = client.request_raw(
+                Formatting::METHOD,
+                json!({
+                    "textDocument": { "uri": file_uri(dir.path(), "unknown.typ") },
+                    "options": { "tabSize": 2, "insertSpaces": true },
+                }),
+            );
+            assert_eq!(
+                response.error.unwrap().code,
+                ErrorCode::InvalidParams as i32
+            );
+        });
+    }
+}
@@ -1,7 +1,12 @@
 mod cli;
+mod config;
+mod diff;
 mod fmt;
 mod fs;
+mod git;
 mod logging;
+mod lsp;
+mod report;
 
 use std::{io::Write, process::ExitCode};
 
@@ -44,6 +49,10 @@ fn main() -> ExitCode {
     } else {
         log::LevelFilter::Info
     });
+    if args.output_format != cli::OutputFormat::Text {
+        // Keep the report on stdout machine-readable.
+        logging::redirect_to_stderr();
+    }
 
     match execute(args) {
         Ok(code) => code.into(),
@@ -58,9 +67,9 @@ fn main() -> ExitCode {
 }
 
 fn execute(args: CliArguments) -> Result<ExitStatus> {
-    #[cfg(feature = "completion")]
     if let Some(command) = &args.command {
         match command {
+            #[cfg(feature = "completion")]
             cli::Command::Completions { shell } => {
                 use clap::CommandFactory;
 
@@ -73,6 +82,7 @@ fn execute(args: CliArguments) -> Result<ExitStatus> {
 
                 return Ok(ExitStatus::Success);
             }
+            cli::Command::Lsp => return lsp::run(&args.style),
         }
     }
 
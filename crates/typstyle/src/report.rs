@@ -0,0 +1,196 @@
+//! Machine-readable reports of the results of 'check' mode.
+use itertools::Itertools;
+use serde::Serialize;
+use serde_json::json;
+use typstyle_core::TextEdit;
+
+use crate::cli::OutputFormat;
+
+/// The collected results of checked files, in the order they were checked.
+#[derive(Default)]
+pub struct Report {
+    files: Vec<FileReport>,
+}
+
+#[derive(Serialize)]
+struct FileReport {
+    path: String,
+    status: FileStatus,
+    /// Line ranges of the original contents that would be changed.
+    ranges: Vec<LineRange>,
+}
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileStatus {
+    /// The file would be reformatted.
+    Changed,
+    /// The file is already formatted.
+    Unchanged,
+    /// The file contains syntax errors, so it is not formatted.
+    Erroneous,
+}
+
+/// A 1-based, inclusive range of lines.
+#[derive(Serialize)]
+struct LineRange {
+    start: usize,
+    end: usize,
+}
+
+impl Report {
+    /// Records the result of a file. `formatted` is only used for changed files.
+    pub fn add(&mut self, path: String, status: FileStatus, original: &str, formatted: &str) {
+        let ranges = match status {
+            FileStatus::Changed => changed_ranges(original, formatted),
+            _ => Vec::new(),
+        };
+        self.files.push(FileReport {
+            path,
+            status,
+            ranges,
+        });
+    }
+
+    /// Renders the report in the given format. Returns `None` for [`OutputFormat::Text`].
+    pub fn render(&self, format: OutputFormat) -> Option<String> {
+        match format {
+            OutputFormat::Text => None,
+            OutputFormat::Json => Some(self.render_json()),
+            OutputFormat::Sarif => Some(self.render_sarif()),
+            OutputFormat::Github => Some(self.render_github()),
+        }
+    }
+
+    fn render_json(&self) -> String {
+        serde_json::to_string_pretty(&self.files).expect("report should be serializable") + "\n"
+    }
+
+    fn render_sarif(&self) -> String {
+        let results = self
+            .files
+            .iter()
+            .flat_map(|file| {
+                let uri = file.path.replace('\\', "/");
+                let (rule, message) = match file.status {
+                    FileStatus::Changed => ("format", "File would be reformatted"),
+                    FileStatus::Erroneous => ("syntax-error", "Failed to parse the file"),
+                    FileStatus::Unchanged => return Vec::new(),
+                };
+                let regions = if file.ranges.is_empty() {
+                    vec![json!({ "startLine": 1 })]
+                } else {
+                    file.ranges
+                        .iter()
+                        .map(|range| json!({ "startLine": range.start, "endLine": range.end }))
+                        .collect()
+                };
+                regions
+                    .into_iter()
+                    .map(|region| {
+                        json!({
+                            "ruleId": rule,
+                            "level": if rule == "format" { "error" } else { "warning" },
+                            "message": { "text": message },
+                            "locations": [{
+                                "physicalLocation": {
+                                    "artifactLocation": { "uri": uri },
+                                    "region": region,
+                                }
+                            }],
+                        })
+                    })
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+        let log = json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "typstyle",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": env!("CARGO_PKG_HOMEPAGE"),
+                        "rules": [
+                            {
+                                "id": "format",
+                                "shortDescription": { "text": "File is not formatted" },
+                            },
+                            {
+                                "id": "syntax-error",
+                                "shortDescription": { "text": "File contains syntax errors" },
+                            },
+                        ],
+                    }
+                },
+                "results": results,
+            }],
+        });
+        serde_json::to_string_pretty(&log).expect("report should be serializable") + "\n"
+    }
+
+    fn render_github(&self) -> String {
+        let mut out = String::new();
+        for file in &self.files {
+            let path = escape_property(&file.path);
+            match file.status {
+                FileStatus::Changed => {
+                    for range in &file.ranges {
+                        out += &format!(
+                            "::error file={path},line={},endLine={},title=typstyle::File would be reformatted\n",
+                            range.start, range.end
+                        );
+                    }
+                }
+                FileStatus::Erroneous => {
+                    out += &format!(
+                        "::warning file={path},title=typstyle::Failed to parse the file\n"
+                    );
+                }
+                FileStatus::Unchanged => {}
+            }
+        }
+        out
+    }
+}
+
+/// Computes the line ranges of `original` that differ from `formatted`.
+fn changed_ranges(original: &str, formatted: &str) -> Vec<LineRange> {
+    let line_count = original.lines().count().max(1);
+    let line_of = |offset: usize| original[..offset].matches('\n').count() + 1;
+    TextEdit::diff(original, formatted)
+        .into_iter()
+        .map(|edit| {
+            let start = line_of(edit.range.start);
+            // A removed line break belongs to the line it ends.
+            let end = line_of(edit.range.end.max(edit.range.start + 1) - 1);
+            // Insertions at the end of the text are attached to the last line.
+            let start = start.min(line_count);
+            LineRange {
+                start,
+                end: end.clamp(start, line_count),
+            }
+        })
+        .coalesce(|prev, next| {
+            if next.start <= prev.end + 1 {
+                Ok(LineRange {
+                    start: prev.start,
+                    end: prev.end.max(next.end),
+                })
+            } else {
+                Err((prev, next))
+            }
+        })
+        .collect()
+}
+
+/// Escapes a property value of a GitHub Actions workflow command.
+fn escape_property(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
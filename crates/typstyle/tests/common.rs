@@ -10,6 +10,7 @@ use std::{
 use insta_cmd::get_cargo_bin;
 use tempfile::TempDir;
 
+#[allow(unused)]
 pub const INSTA_FILTERS: &[(&str, &str)] = &[
     (r"(?m)^(.+) in [\w.]+$", "$1 in [DURATION]"),
     (
@@ -0,0 +1,345 @@
+mod common;
+
+use common::{typstyle_cmd_snapshot, Workspace};
+
+const STDIN: &str = "#let f(x) = {
+for i in range(0, 5) {
+     x = x + i
+ }
+}";
+
+#[test]
+fn test_config_file() {
+    let space = Workspace::new();
+    space.write("typstyle.toml", "tab_spaces = 4\n");
+
+    typstyle_cmd_snapshot!(space.cli().pass_stdin(STDIN), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    #let f(x) = {
+        for i in range(0, 5) {
+            x = x + i
+        }
+    }
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_config_file_overridden() {
+    let space = Workspace::new();
+    space.write("typstyle.toml", "tab_spaces = 4\n");
+
+    typstyle_cmd_snapshot!(space.cli().args(["-t=3"]).pass_stdin(STDIN), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    #let f(x) = {
+       for i in range(0, 5) {
+          x = x + i
+       }
+    }
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_config_file_indent_style() {
+    let space = Workspace::new();
+    space.write("typstyle.toml", "tab_spaces = 4\nindent_style = \"tab\"\n");
+    space.write("a.typ", STDIN);
+    space.write("b.typ", STDIN);
+
+    typstyle_cmd_snapshot!(space.cli().args(["a.typ", "-i"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+    assert_eq!(
+        space.read_string("a.typ"),
+        "#let f(x) = {\n\tfor i in range(0, 5) {\n\t\tx = x + i\n\t}\n}\n"
+    );
+
+    typstyle_cmd_snapshot!(space.cli().args(["b.typ", "-i", "--indent-style=space"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+    assert_eq!(
+        space.read_string("b.typ"),
+        "#let f(x) = {\n    for i in range(0, 5) {\n        x = x + i\n    }\n}\n"
+    );
+}
+
+#[test]
+fn test_config_file_ignored() {
+    let space = Workspace::new();
+    space.write("typstyle.toml", "tab_spaces = 4\n");
+
+    typstyle_cmd_snapshot!(space.cli().args(["--no-config"]).pass_stdin(STDIN), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    #let f(x) = {
+      for i in range(0, 5) {
+        x = x + i
+      }
+    }
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_config_file_explicit() {
+    let space = Workspace::new();
+    space.write("typstyle.toml", "tab_spaces = 4\n");
+    space.write("conf/style.toml", "tab_spaces = 8\n");
+
+    typstyle_cmd_snapshot!(space.cli().args(["--config", "conf/style.toml"]).pass_stdin(STDIN), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    #let f(x) = {
+            for i in range(0, 5) {
+                    x = x + i
+            }
+    }
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_config_file_nearest() {
+    let mut space = Workspace::new();
+    space.write("typstyle.toml", "max_width = 20\n");
+    space.write("sub/typstyle.toml", "tab_spaces = 4\n");
+    space.write_tracked("a.typ", "#let a = (1, 2, 3, 4, 5, 6, 7)");
+    space.write_tracked("sub/b.typ", "#let b = {\n1\n}");
+
+    typstyle_cmd_snapshot!(space.cli().args([".", "-i"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+
+    assert_eq!(
+        space.read_string("a.typ"),
+        "#let a = (\n  1,\n  2,\n  3,\n  4,\n  5,\n  6,\n  7,\n)\n"
+    );
+    assert_eq!(space.read_string("sub/b.typ"), "#let b = {\n    1\n}\n");
+}
+
+#[test]
+fn test_config_manifest() {
+    let space = Workspace::new();
+    space.write(
+        "typst.toml",
+        r#"[package]
+name = "example"
+version = "0.1.0"
+entrypoint = "lib.typ"
+
+[tool.typstyle]
+tab_spaces = 4
+"#,
+    );
+
+    typstyle_cmd_snapshot!(space.cli().pass_stdin(STDIN), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    #let f(x) = {
+        for i in range(0, 5) {
+            x = x + i
+        }
+    }
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_config_manifest_without_section() {
+    let space = Workspace::new();
+    space.write("typstyle.toml", "tab_spaces = 4\n");
+    space.write(
+        "sub/typst.toml",
+        r#"[package]
+name = "example"
+version = "0.1.0"
+entrypoint = "lib.typ"
+"#,
+    );
+    space.write("sub/a.typ", STDIN);
+
+    typstyle_cmd_snapshot!(space.cli().arg("sub/a.typ"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    #let f(x) = {
+        for i in range(0, 5) {
+            x = x + i
+        }
+    }
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_config_file_invalid() {
+    let space = Workspace::new();
+    space.write("typstyle.toml", "tab_spaces = \"four\"\n");
+
+    typstyle_cmd_snapshot!(space.cli().pass_stdin(STDIN), @r#"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+      Cause: invalid config in [TEMP_PATH]/project/typstyle.toml
+      Cause: invalid type: string "four", expected usize
+    in `tab_spaces`
+    "#);
+}
+
+#[test]
+fn test_config_file_unknown_key() {
+    let space = Workspace::new();
+    space.write("typstyle.toml", "tab_space = 4\n");
+
+    typstyle_cmd_snapshot!(space.cli().pass_stdin(STDIN), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+      Cause: invalid config in [TEMP_PATH]/project/typstyle.toml
+      Cause: unknown field `tab_space`, expected one of `tab_spaces`, `indent_style`, `newline_style`, `max_width`, `trailing_comma`, `enum_marker`, `blank_lines_upper_bound`, `blank_lines_before_heading`, `blank_lines_after_heading`, `blank_lines_around_block`, `collapse_markup_spaces`, `reorder_import_items`, `reorder_import_statements`, `remove_unused_imports`, `wrap_text`, `sentence_abbreviations`, `tolerate_errors`
+    ");
+}
+
+#[test]
+fn test_config_file_flag_disabled() {
+    let space = Workspace::new();
+    space.write(
+        "typstyle.toml",
+        "reorder_import_items = false\ntolerate_errors = true\n",
+    );
+    let imports = "#import \"a.typ\": c, b\n";
+    let erroneous = "#let a  =  1\n#let b =\n";
+
+    typstyle_cmd_snapshot!(space.cli().args(["--reorder-import-items"]).pass_stdin(imports), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    #import "a.typ": b, c
+
+    ----- stderr -----
+    "#);
+    typstyle_cmd_snapshot!(space.cli().args(["--no-tolerate-errors"]).pass_stdin(erroneous), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    #let a  =  1
+    #let b =
+
+    ----- stderr -----
+    warn: Failed to parse stdin. The source is erroneous.
+      stdin:2:9: expected expression
+    ");
+    // The last flag of a pair wins.
+    typstyle_cmd_snapshot!(space.cli().args(["--tolerate-errors", "--no-tolerate-errors", "--tolerate-errors"]).pass_stdin(erroneous), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    #let a = 1
+    #let b =
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_config_file_inherited() {
+    let mut space = Workspace::new();
+    space.write("typstyle.toml", "max_width = 20\n");
+    space.write("sub/typstyle.toml", "tab_spaces = 4\n");
+    space.write("sub/vendor/typstyle.toml", "root = true\nmax_width = 120\n");
+    space.write_tracked("sub/a.typ", "#let a = (1, 2, 3, 4, 5, 6, 7)");
+    space.write_tracked("sub/vendor/b.typ", "#let b = (1, 2, 3, 4, 5, 6, 7)");
+
+    typstyle_cmd_snapshot!(space.cli().args([".", "-i"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+
+    assert_eq!(
+        space.read_string("sub/a.typ"),
+        "#let a = (\n    1,\n    2,\n    3,\n    4,\n    5,\n    6,\n    7,\n)\n"
+    );
+    assert_eq!(
+        space.read_string("sub/vendor/b.typ"),
+        "#let b = (1, 2, 3, 4, 5, 6, 7)\n"
+    );
+}
+
+#[test]
+fn test_config_file_invalid_root() {
+    let space = Workspace::new();
+    space.write("typstyle.toml", "root = 1\n");
+
+    typstyle_cmd_snapshot!(space.cli().pass_stdin(STDIN), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+      Cause: `root` must be a boolean in [TEMP_PATH]/project/typstyle.toml
+    ");
+}
+
+#[test]
+fn test_config_file_wrap_text() {
+    let space = Workspace::new();
+    space.write("typstyle.toml", "max_width = 20\nwrap_text = true\n");
+    space.write("sentence/typstyle.toml", "wrap_text = \"sentence\"\n");
+    space.write(
+        "a.typ",
+        "lorem ipsum dolor sit amet. Consectetur adipiscing elit.",
+    );
+    space.write(
+        "sentence/b.typ",
+        "lorem ipsum dolor sit amet. Consectetur adipiscing elit.",
+    );
+
+    typstyle_cmd_snapshot!(space.cli().args(["a.typ", "sentence/b.typ", "-i"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+    assert_eq!(
+        space.read_string("a.typ"),
+        "lorem ipsum dolor\nsit amet.\nConsectetur\nadipiscing elit.\n"
+    );
+    assert_eq!(
+        space.read_string("sentence/b.typ"),
+        "lorem ipsum dolor sit amet.\nConsectetur adipiscing elit.\n"
+    );
+}
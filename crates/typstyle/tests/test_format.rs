@@ -64,6 +64,7 @@ fn test_one_erroneous() {
     #let
     ----- stderr -----
     warn: Failed to parse a.typ. The source is erroneous.
+      a.typ:1:5: expected pattern
     ");
 
     assert!(space.all_unmodified());
@@ -81,6 +82,38 @@ fn test_one_inplace_erroneous() {
 
     ----- stderr -----
     warn: Failed to parse a.typ. The source is erroneous.
+      a.typ:1:5: expected pattern
+    ");
+
+    assert!(space.all_unmodified());
+}
+
+#[test]
+fn test_one_directive_warnings() {
+    let mut space = Workspace::new();
+    space.write_tracked(
+        "a.typ",
+        "// @typstyle on\n\n// @typstyle off\n\n#let a  =  0\n\n// @typstyle skip(tables) max_width=x\n#let b  =  1\n",
+    );
+
+    typstyle_cmd_snapshot!(space.cli().args(["a.typ"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    // @typstyle on
+
+    // @typstyle off
+
+    #let a = 0
+
+    // @typstyle skip(tables) max_width=x
+    #let b = 1
+
+    ----- stderr -----
+    warn: a.typ:1: `@typstyle on` without a preceding `@typstyle off`
+    warn: a.typ:3: directive has no effect, as it is not directly followed by an item
+    warn: a.typ:7: unknown layout feature `tables`
+    warn: a.typ:7: invalid value `x` for `max_width`, expected a number
     ");
 
     assert!(space.all_unmodified());
@@ -374,3 +407,68 @@ fn test_many_check() {
 
     assert!(space.all_unmodified());
 }
+
+#[test]
+fn test_newline_auto() {
+    let mut space = Workspace::new();
+    space.write_tracked("a.typ", "#let a  =  0\r\n#{\r\n1\r\n}\r\n");
+    space.write_tracked("b.typ", "#let b = 1\r\n");
+
+    typstyle_cmd_snapshot!(space.cli().args(["a.typ", "b.typ", "-i"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+
+    assert_eq!(
+        space.read_string("a.typ"),
+        "#let a = 0\r\n#{\r\n  1\r\n}\r\n"
+    );
+    assert!(space.is_unmodified("b.typ"));
+}
+
+#[test]
+fn test_newline_style() {
+    let space = Workspace::new();
+    space.write("a.typ", "#let a  =  0\r\n#let b = 1\r\n");
+    space.write("b.typ", "#let a  =  0\n#let b = 1\n");
+
+    typstyle_cmd_snapshot!(space.cli().args(["a.typ", "-i", "--newline-style=lf"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+    typstyle_cmd_snapshot!(space.cli().args(["b.typ", "-i", "--newline-style=crlf"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+
+    assert_eq!(space.read_string("a.typ"), "#let a = 0\n#let b = 1\n");
+    assert_eq!(space.read_string("b.typ"), "#let a = 0\r\n#let b = 1\r\n");
+}
+
+#[test]
+fn test_newline_lines() {
+    let space = Workspace::new();
+    space.write("a.typ", "#let a  =  0\r\n#let f(x)  =  {\r\nx\r\n}\r\n");
+
+    typstyle_cmd_snapshot!(space.cli().args(["a.typ", "-i", "--lines=2-4"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+
+    assert_eq!(
+        space.read_string("a.typ"),
+        "#let a  =  0\r\n#let f(x) = {\r\n  x\r\n}\r\n"
+    );
+}
@@ -144,6 +144,113 @@ fn test_all_2_check() {
     assert!(space.all_unmodified());
 }
 
+#[test]
+fn test_all_diff() {
+    let mut space = Workspace::new();
+    space.write_tracked("a.typ", "#let a = 0\n");
+    space.write_tracked(
+        "x/b.typ",
+        "= Title\n\n#let b  =  1\n\nSome text.\n\nMore text.\n\nEven more.\n\n#let c  =  2\n",
+    );
+
+    typstyle_cmd_snapshot!(space.cli().args([".", "--diff"]), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    --- x/b.typ
+    +++ x/b.typ
+    @@ -1,6 +1,6 @@
+     = Title
+     
+    -#let b  =  1
+    +#let b = 1
+     
+     Some text.
+     
+    @@ -8,4 +8,4 @@
+     
+     Even more.
+     
+    -#let c  =  2
+    +#let c = 2
+
+    ----- stderr -----
+    ");
+
+    assert!(space.all_unmodified());
+}
+
+#[test]
+fn test_all_diff_inplace() {
+    let mut space = Workspace::new();
+    space.write_tracked("a.typ", "#let a  =  0");
+
+    typstyle_cmd_snapshot!(space.cli().args([".", "-i", "--diff"]), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: the argument '--inplace' cannot be used with '--diff'
+
+    Usage: typstyle --inplace <INPUT>...
+
+    For more information, try '--help'.
+    ");
+
+    assert!(space.all_unmodified());
+}
+
+#[test]
+fn test_all_jobs() {
+    let mut space = Workspace::new();
+    for i in 0..8 {
+        space.write_tracked(format!("f{i}.typ"), format!("#let x{i}  =  {i}"));
+    }
+    space.write_tracked("f8.typ", "#let x8 = 8\n");
+    space.write_tracked("f9.typ", "#let x9 = (");
+
+    typstyle_cmd_snapshot!(space.cli().args([".", "--check", "-j", "4", "-v"]), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    Would reformat: f0.typ
+    Would reformat: f1.typ
+    Would reformat: f2.typ
+    Would reformat: f3.typ
+    Would reformat: f4.typ
+    Would reformat: f5.typ
+    Would reformat: f6.typ
+    Would reformat: f7.typ
+    8 files would be reformatted (2 already formatted), checked in [DURATION]
+
+    ----- stderr -----
+    warn: Failed to parse f9.typ. The source is erroneous.
+      f9.typ:1:11: unclosed delimiter
+    ");
+
+    typstyle_cmd_snapshot!(space.cli().args([".", "-j", "4"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    #let x0 = 0
+    #let x1 = 1
+    #let x2 = 2
+    #let x3 = 3
+    #let x4 = 4
+    #let x5 = 5
+    #let x6 = 6
+    #let x7 = 7
+    #let x8 = 8
+    #let x9 = (
+    ----- stderr -----
+    warn: Failed to parse f9.typ. The source is erroneous.
+      f9.typ:1:11: unclosed delimiter
+    ");
+
+    assert!(space.all_unmodified());
+}
+
 #[test]
 fn test_all_erroneous() {
     let mut space = Workspace::new();
@@ -159,6 +266,7 @@ fn test_all_erroneous() {
 
     ----- stderr -----
     warn: Failed to parse x/y/c.typ. The source is erroneous.
+      x/y/c.typ:1:16: expected expression
     ");
 
     assert!(space.is_unmodified("a.typ"));
@@ -233,3 +341,102 @@ fn test_dir_all_check() {
     For more information, try '--help'.
     ");
 }
+
+#[test]
+fn test_all_ignore_files() {
+    let mut space = Workspace::new();
+    space.write(".gitignore", "build/\n");
+    space.write(".typstyleignore", "*.gen.typ\n");
+    space.write_tracked("a.typ", "#let a  =  0");
+    space.write_tracked("a.gen.typ", "#let a  =  0");
+    space.write_tracked("build/b.typ", "#let b  =  1");
+
+    typstyle_cmd_snapshot!(space.cli().args([".", "-i", "-v"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Successfully formatted 1 file (0 unchanged) in [DURATION]
+
+    ----- stderr -----
+    ");
+
+    assert_eq!(space.read_string("a.typ"), "#let a = 0\n");
+    assert!(space.is_unmodified("a.gen.typ"));
+    assert!(space.is_unmodified("build/b.typ"));
+
+    typstyle_cmd_snapshot!(space.cli().args([".", "-i", "-v", "--no-ignore"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Successfully formatted 2 files (1 unchanged) in [DURATION]
+
+    ----- stderr -----
+    ");
+
+    assert_eq!(space.read_string("a.gen.typ"), "#let a = 0\n");
+    assert_eq!(space.read_string("build/b.typ"), "#let b = 1\n");
+}
+
+#[test]
+fn test_all_exclude_include() {
+    let mut space = Workspace::new();
+    space.write_tracked("a.typ", "#let a  =  0");
+    space.write_tracked("x/b.typ", "#let b  =  1");
+    space.write_tracked("x/c.typ", "#let c  =  2");
+    space.write_tracked("fixtures/d.typ", "#let d  =  3");
+
+    typstyle_cmd_snapshot!(space.cli().args([".", "-i", "-v", "--exclude", "fixtures", "--include", "x/*"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Successfully formatted 2 files (0 unchanged) in [DURATION]
+
+    ----- stderr -----
+    ");
+
+    assert!(space.is_unmodified("a.typ"));
+    assert_eq!(space.read_string("x/b.typ"), "#let b = 1\n");
+    assert_eq!(space.read_string("x/c.typ"), "#let c = 2\n");
+    assert!(space.is_unmodified("fixtures/d.typ"));
+
+    // Explicitly given files are always formatted.
+    typstyle_cmd_snapshot!(space.cli().args(["fixtures/d.typ", "-i", "-v", "--exclude", "fixtures"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Successfully formatted 1 file (0 unchanged) in [DURATION]
+
+    ----- stderr -----
+    ");
+
+    assert_eq!(space.read_string("fixtures/d.typ"), "#let d = 3\n");
+}
+
+#[test]
+fn test_all_exclude_config() {
+    let mut space = Workspace::new();
+    space.write("typstyle.toml", "exclude = [\"*.gen.typ\"]\n");
+    space.write("x/typstyle.toml", "exclude = [\"/vendor\"]\n");
+    space.write_tracked("a.gen.typ", "#let a  =  0");
+    space.write_tracked("x/b.gen.typ", "#let b  =  1");
+    space.write_tracked("x/c.typ", "#let c  =  2");
+    space.write_tracked("x/vendor/d.typ", "#let d  =  3");
+    space.write_tracked("vendor/e.typ", "#let e  =  4");
+
+    typstyle_cmd_snapshot!(space.cli().args([".", "-i", "-v"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Using config file: x/typstyle.toml
+    Using config file: typstyle.toml
+    Successfully formatted 2 files (0 unchanged) in [DURATION]
+
+    ----- stderr -----
+    ");
+
+    assert!(space.is_unmodified("a.gen.typ"));
+    assert!(space.is_unmodified("x/b.gen.typ"));
+    assert_eq!(space.read_string("x/c.typ"), "#let c = 2\n");
+    assert!(space.is_unmodified("x/vendor/d.typ"));
+    assert_eq!(space.read_string("vendor/e.typ"), "#let e = 4\n");
+}
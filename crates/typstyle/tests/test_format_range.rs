@@ -0,0 +1,166 @@
+mod common;
+
+use common::{typstyle_cmd_snapshot, Workspace};
+
+const SOURCE: &str = "#let a  =  1
+#let b  =  2
+
+#let c  =  3
+";
+
+#[test]
+fn test_lines_stdin() {
+    let space = Workspace::new();
+
+    typstyle_cmd_snapshot!(space.cli().args(["--lines", "2"]).pass_stdin(SOURCE), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    #let a  =  1
+    #let b = 2
+
+    #let c  =  3
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lines_multiple() {
+    let space = Workspace::new();
+
+    typstyle_cmd_snapshot!(space.cli().args(["--lines", "1-1", "--lines", "3-4"]).pass_stdin(SOURCE), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    #let a = 1
+    #let b  =  2
+
+    #let c = 3
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lines_blank() {
+    let space = Workspace::new();
+
+    typstyle_cmd_snapshot!(space.cli().args(["--lines", "3", "--check"]).pass_stdin(SOURCE), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_lines_invalid() {
+    let space = Workspace::new();
+
+    typstyle_cmd_snapshot!(space.cli().args(["--lines", "4-2"]).pass_stdin(SOURCE), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: invalid value '4-2' for '--lines <START-END>': the start line 4 is after the end line 2
+
+    For more information, try '--help'.
+    ");
+}
+
+fn git(space: &Workspace, args: &[&str]) {
+    let status = space
+        .cmd("git")
+        .args([
+            "-c",
+            "user.name=typstyle",
+            "-c",
+            "user.email=typstyle@example.com",
+        ])
+        .args(args)
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success(), "git {args:?} failed");
+}
+
+#[test]
+fn test_changed_since() {
+    let space = Workspace::new();
+    git(&space, &["init", "-q"]);
+    space.write("a.typ", SOURCE);
+    space.write("b.typ", SOURCE);
+    git(&space, &["add", "."]);
+    git(&space, &["commit", "-q", "-m", "init"]);
+
+    space.write("a.typ", SOURCE.replace("#let c  =  3", "#let c  =  4"));
+    space.write("c.typ", "#let d  =  5\n");
+
+    typstyle_cmd_snapshot!(space.cli().args([".", "-i", "--changed-since", "HEAD"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+
+    assert_eq!(
+        space.read_string("a.typ"),
+        "#let a  =  1\n#let b  =  2\n\n#let c = 4\n"
+    );
+    assert_eq!(space.read_string("b.typ"), SOURCE);
+    assert_eq!(space.read_string("c.typ"), "#let d = 5\n");
+}
+
+#[test]
+fn test_changed_since_special_paths() {
+    let space = Workspace::new();
+    git(&space, &["init", "-q"]);
+    // Git appends a tab to paths with spaces, and quotes those with special characters.
+    let names = ["my notes.typ", "naïve.typ", "say \"hi\".typ"];
+    for name in names {
+        space.write(name, SOURCE);
+    }
+    git(&space, &["add", "."]);
+    git(&space, &["commit", "-q", "-m", "init"]);
+
+    for name in names {
+        space.write(name, SOURCE.replace("#let c  =  3", "#let c  =  4"));
+    }
+    space.write("new file.typ", "#let d  =  5\n");
+
+    typstyle_cmd_snapshot!(space.cli().args([".", "-i", "--changed-since", "HEAD"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+
+    for name in names {
+        assert_eq!(
+            space.read_string(name),
+            "#let a  =  1\n#let b  =  2\n\n#let c = 4\n",
+            "{name}"
+        );
+    }
+    assert_eq!(space.read_string("new file.typ"), "#let d = 5\n");
+}
+
+#[test]
+fn test_changed_since_not_a_repo() {
+    let space = Workspace::new();
+    space.write("a.typ", SOURCE);
+
+    typstyle_cmd_snapshot!(space.cli().args(["a.typ", "--changed-since", "HEAD"]), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+      Cause: `git rev-parse --show-toplevel` failed: fatal: not a git repository (or any of the parent directories): .git
+    ");
+}
@@ -43,6 +43,7 @@ fn test_stdin_erroneous() {
     #
     ----- stderr -----
     warn: Failed to parse stdin. The source is erroneous.
+      stdin:1:2: expected expression
     ");
 }
 
@@ -76,6 +77,38 @@ fn test_stdin_check() {
     ");
 }
 
+#[test]
+fn test_stdin_diff() {
+    let space = Workspace::new();
+
+    typstyle_cmd_snapshot!(space.cli().args(["--diff"]).pass_stdin(STDIN), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    --- stdin
+    +++ stdin
+    @@ -1 +1 @@
+    -#let  x  = (1+2)
+    \ No newline at end of file
+    +#let x = (1 + 2)
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_stdin_diff_unchanged() {
+    let space = Workspace::new();
+
+    typstyle_cmd_snapshot!(space.cli().args(["--diff"]).pass_stdin("#let x = (1 + 2)\n"), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    ");
+}
+
 #[test]
 fn test_stdin_inplace() {
     let space = Workspace::new();
@@ -88,7 +121,7 @@ fn test_stdin_inplace() {
     ----- stderr -----
     error: cannot perform in-place formatting without at least one file being presented
 
-    Usage: typstyle [OPTIONS] [INPUT]...
+    Usage: typstyle [OPTIONS] [INPUT]... [COMMAND]
 
     For more information, try '--help'.
     ");
@@ -0,0 +1,231 @@
+mod common;
+
+use common::{typstyle_cmd_snapshot, Workspace};
+
+fn workspace() -> Workspace {
+    let mut space = Workspace::new();
+    space.write_tracked("a.typ", "#let a = 0\n");
+    space.write_tracked(
+        "b.typ",
+        "= Title\n\n#let b  =  1\n\nText.\n\n#let c  =  2\n",
+    );
+    space.write_tracked("c.typ", "#let c = (\n");
+    space
+}
+
+#[test]
+fn test_output_format_json() {
+    let space = workspace();
+
+    typstyle_cmd_snapshot!(space.cli().args([".", "--check", "--output-format", "json"]), @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    [
+      {
+        "path": "a.typ",
+        "status": "unchanged",
+        "ranges": []
+      },
+      {
+        "path": "b.typ",
+        "status": "changed",
+        "ranges": [
+          {
+            "start": 3,
+            "end": 3
+          },
+          {
+            "start": 7,
+            "end": 7
+          }
+        ]
+      },
+      {
+        "path": "c.typ",
+        "status": "erroneous",
+        "ranges": []
+      }
+    ]
+
+    ----- stderr -----
+    warn: Failed to parse c.typ. The source is erroneous.
+      c.typ:1:10: unclosed delimiter
+    "#);
+
+    assert!(space.all_unmodified());
+}
+
+#[test]
+fn test_output_format_verbose() {
+    let mut space = Workspace::new();
+    space.write_tracked("a.typ", "#let a  =  0\n");
+
+    // Logs must not be mixed into the report.
+    typstyle_cmd_snapshot!(space.cli().args([".", "--check", "--output-format", "json", "-v"]), @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    [
+      {
+        "path": "a.typ",
+        "status": "changed",
+        "ranges": [
+          {
+            "start": 1,
+            "end": 1
+          }
+        ]
+      }
+    ]
+
+    ----- stderr -----
+    1 file would be reformatted (0 already formatted), checked in [DURATION]
+    "#);
+}
+
+#[test]
+fn test_output_format_github() {
+    let space = workspace();
+
+    typstyle_cmd_snapshot!(space.cli().args([".", "--check", "-q", "--output-format", "github"]), @r"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    ::error file=b.typ,line=3,endLine=3,title=typstyle::File would be reformatted
+    ::error file=b.typ,line=7,endLine=7,title=typstyle::File would be reformatted
+    ::warning file=c.typ,title=typstyle::Failed to parse the file
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_output_format_sarif() {
+    let space = workspace();
+
+    insta::with_settings!({ filters => vec![(env!("CARGO_PKG_VERSION"), "[VERSION]")] }, {
+        typstyle_cmd_snapshot!(space.cli().args(["b.typ", "--check", "-q", "--output-format", "sarif"]), @r#"
+        success: false
+        exit_code: 1
+        ----- stdout -----
+        {
+          "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+          "version": "2.1.0",
+          "runs": [
+            {
+              "tool": {
+                "driver": {
+                  "name": "typstyle",
+                  "version": "[VERSION]",
+                  "informationUri": "https://typstyle-rs.github.io/typstyle/",
+                  "rules": [
+                    {
+                      "id": "format",
+                      "shortDescription": {
+                        "text": "File is not formatted"
+                      }
+                    },
+                    {
+                      "id": "syntax-error",
+                      "shortDescription": {
+                        "text": "File contains syntax errors"
+                      }
+                    }
+                  ]
+                }
+              },
+              "results": [
+                {
+                  "ruleId": "format",
+                  "level": "error",
+                  "message": {
+                    "text": "File would be reformatted"
+                  },
+                  "locations": [
+                    {
+                      "physicalLocation": {
+                        "artifactLocation": {
+                          "uri": "b.typ"
+                        },
+                        "region": {
+                          "startLine": 3,
+                          "endLine": 3
+                        }
+                      }
+                    }
+                  ]
+                },
+                {
+                  "ruleId": "format",
+                  "level": "error",
+                  "message": {
+                    "text": "File would be reformatted"
+                  },
+                  "locations": [
+                    {
+                      "physicalLocation": {
+                        "artifactLocation": {
+                          "uri": "b.typ"
+                        },
+                        "region": {
+                          "startLine": 7,
+                          "endLine": 7
+                        }
+                      }
+                    }
+                  ]
+                }
+              ]
+            }
+          ]
+        }
+
+        ----- stderr -----
+        "#);
+    });
+}
+
+#[test]
+fn test_output_format_stdin() {
+    let space = Workspace::new();
+
+    typstyle_cmd_snapshot!(space.cli().args(["--check", "--output-format", "json"]).pass_stdin("#let  x  = 1"), @r#"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    [
+      {
+        "path": "stdin",
+        "status": "changed",
+        "ranges": [
+          {
+            "start": 1,
+            "end": 1
+          }
+        ]
+      }
+    ]
+
+    ----- stderr -----
+    "#);
+}
+
+#[test]
+fn test_output_format_without_check() {
+    let space = Workspace::new();
+
+    typstyle_cmd_snapshot!(space.cli().args(["--output-format", "json"]).pass_stdin("#let  x  = 1"), @r"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: the following required arguments were not provided:
+      --check
+
+    Usage: typstyle --check --output-format <OUTPUT_FORMAT> [INPUT]...
+
+    For more information, try '--help'.
+    ");
+}
@@ -68,3 +68,141 @@ fn test_wrap_text() {
     ----- stderr -----
     ");
 }
+
+#[test]
+fn test_wrap_text_sentence() {
+    let space = Workspace::new();
+
+    let stdin = "First sentence, which was\nhard-wrapped. Second one, e.g. this. Third!";
+
+    typstyle_cmd_snapshot!(space.cli().args(["--wrap-text=sentence"]).pass_stdin(stdin), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    First sentence, which was hard-wrapped.
+    Second one, e.g. this.
+    Third!
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_fix_unused_imports() {
+    let space = Workspace::new();
+
+    let stdin = r#"#import "a.typ": foo, bar
+#import "b.typ": baz
+#import "c.typ": *
+
+#foo"#;
+
+    typstyle_cmd_snapshot!(space.cli().args(["--fix", "unused-imports"]).pass_stdin(stdin), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    #import "a.typ": foo
+    #import "c.typ": *
+
+    #foo
+
+    ----- stderr -----
+    "#);
+}
+
+#[test]
+fn test_fix_unused_imports_entrypoint() {
+    let space = Workspace::new();
+    space.write(
+        "typst.toml",
+        r#"[package]
+name = "example"
+version = "0.1.0"
+entrypoint = "lib.typ"
+"#,
+    );
+    let source = "#import \"utils.typ\": foo, bar\n\n#foo\n";
+    space.write("lib.typ", source);
+    space.write("src/a.typ", source);
+
+    typstyle_cmd_snapshot!(space.cli().args(["--fix", "unused-imports", "lib.typ"]), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    #import "utils.typ": bar, foo
+
+    #foo
+
+    ----- stderr -----
+    "#);
+    typstyle_cmd_snapshot!(space.cli().args(["--fix", "unused-imports", "src/a.typ"]), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    #import "utils.typ": foo
+
+    #foo
+
+    ----- stderr -----
+    "#);
+}
+
+#[test]
+fn test_fix_unused_imports_imported() {
+    let space = Workspace::new();
+    space.write("utils.typ", "#import \"helpers.typ\": foo, bar\n");
+    space.write("main.typ", "#import \"utils.typ\": foo, bar\n\n#foo\n");
+
+    typstyle_cmd_snapshot!(space.cli().args(["--fix", "unused-imports", "utils.typ"]), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    #import "helpers.typ": bar, foo
+
+    ----- stderr -----
+    "#);
+    typstyle_cmd_snapshot!(space.cli().args(["--fix", "unused-imports", "main.typ"]), @r#"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    #import "utils.typ": foo
+
+    #foo
+
+    ----- stderr -----
+    "#);
+}
+
+#[test]
+fn test_tolerate_errors() {
+    let space = Workspace::new();
+    space.write("a.typ", "#let a  =  (1,2)\n#let b =\n#let c  =  3\n");
+
+    typstyle_cmd_snapshot!(space.cli().args(["--tolerate-errors", "a.typ"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    #let a = (1, 2)
+    #let b =
+    #let c = 3
+
+    ----- stderr -----
+    ");
+}
+
+#[test]
+fn test_tolerate_errors_markup() {
+    let space = Workspace::new();
+    space.write("a.typ", "a *b\n\nc  #f(x,y)\n");
+
+    typstyle_cmd_snapshot!(space.cli().args(["--tolerate-errors", "a.typ"]), @r"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    a *b
+
+    c  #f(x, y)
+
+    ----- stderr -----
+    ");
+}
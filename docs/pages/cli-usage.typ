@@ -34,6 +34,35 @@ typstyle file1.typ file2.typ
 typstyle src/ docs/
 ```
 
+=== File Selection
+
+When searching directories, typstyle skips hidden files and directories, as well as files ignored by `.gitignore`, `.ignore` or `.typstyleignore` files. These ignore files use the gitignore syntax.
+
+```bash
+# Skip files matching gitignore-style patterns, relative to the current directory
+typstyle -i . --exclude "vendor/" --exclude "*.gen.typ"
+
+# Only format files matching the patterns
+typstyle -i . --include "chapters/"
+
+# Do not respect ignore files
+typstyle -i . --no-ignore
+```
+
+Files given explicitly on the command line are always formatted, regardless of these filters.
+
+=== Partial Formatting
+
+```bash
+# Only format lines 10 to 40 (can be repeated)
+typstyle -i file.typ --lines 10-40
+
+# Only format lines changed since a git revision, including uncommitted changes
+typstyle -i . --changed-since main
+```
+
+Each range is expanded to the smallest syntax node that covers it, so slightly more than the given lines may be formatted. With `--changed-since`, untracked files are formatted entirely, and unchanged files are left untouched. This helps adopting typstyle in an existing project without reformatting unrelated code.
+
 == Main Options
 
 === Format Control
@@ -53,6 +82,38 @@ typstyle --inplace file.typ
 typstyle --check src/
 ```
 
+==== Diff Mode
+
+```bash
+# Like --check, but also print a unified diff of the changes
+typstyle --diff src/
+cat file.typ | typstyle --diff
+```
+
+The diff is colored when printed to a terminal.
+
+==== Parallel Formatting
+
+```bash
+# Format with 4 threads (default: number of CPUs)
+typstyle -i -j 4 src/
+```
+
+Files are formatted in parallel, but the output is always printed in the same order.
+
+==== Output Format
+
+```bash
+# Print a machine-readable report in check mode
+typstyle --check --output-format json src/
+typstyle --check --output-format sarif src/ > typstyle.sarif
+
+# Show annotations on pull requests in GitHub Actions
+typstyle --check --output-format github src/
+```
+
+The report lists each checked file, whether it would be reformatted or contains syntax errors, and the line ranges that would change. Available formats are `text` (default), `json`, `sarif` and `github`.
+
 === Format Configuration
 
 ==== Line Width
@@ -69,15 +130,123 @@ typstyle --line-width 100 file.typ
 # Set indentation width (default: 2)
 typstyle -t 4 file.typ
 typstyle --indent-width 4 file.typ
+
+# Indent with tabs instead of spaces (default: space)
+typstyle --indent-style tab file.typ
+```
+
+With tab indentation, the indentation width is the width of a tab, which is used to fit lines within the line width. Alignment beyond the indentation level, such as in raw blocks, still uses spaces.
+
+==== Line Endings
+
+```bash
+# Use CRLF line endings (default: auto)
+typstyle --newline-style crlf file.typ
 ```
 
+Available styles are `auto`, `lf`, `crlf` and `native`. With `auto`, the line ending of the first line of the input is kept, so files with CRLF line endings are not converted. `native` uses CRLF on Windows and LF elsewhere.
+
 ==== Text Wrapping
 
 ```bash
 # Wrap text in markup to fit line width
 typstyle --wrap-text file.typ
+
+# Put each sentence on its own line
+typstyle --wrap-text=sentence file.typ
+
+# Join the lines of each paragraph into a single line
+typstyle --wrap-text=unwrap file.typ
+```
+
+==== Fixes
+
+```bash
+# Remove import items that are never referenced
+typstyle --fix unused-imports file.typ
+```
+
+Fixes change the code beyond its layout, so they are only applied when requested. With `unused-imports`, the entrypoint of a package, as declared in `typst.toml`, is skipped, since its imports are re-exported to the users of the package. So is any file imported by another file of the project, such as a `utils.typ` that imports names only for other files to import them from it. The project is the nearest directory with a `typst.toml` or a git repository, or else the current directory.
+
+==== Syntax Errors
+
+```bash
+# Format files with syntax errors, keeping the erroneous parts unchanged
+typstyle --tolerate-errors file.typ
+```
+
+By default, files with syntax errors are left unchanged, and a warning lists the errors. With `--tolerate-errors`, top-level items, and the items of code and content blocks, that have no errors are still formatted, while those with errors are kept verbatim. This is useful for formatting on save while typing.
+
+=== Configuration File
+
+Instead of repeating options on the command line, you can put them in a `typstyle.toml` file:
+
+```toml
+max_width = 100
+tab_spaces = 4
+wrap_text = true
+```
+
+Alternatively, put them in the `[tool.typstyle]` section of the package manifest `typst.toml`:
+
+```toml
+[package]
+name = "my-package"
+version = "0.1.0"
+entrypoint = "lib.typ"
+
+[tool.typstyle]
+max_width = 100
+```
+
+For each input file, typstyle looks for configuration files in its directory and all parent directories. For stdin, the search starts from the current directory. Options in a nearer file override those in its parent directories, so a subdirectory only needs to specify what differs:
+
+```toml
+# docs/typstyle.toml, inheriting other options from ./typstyle.toml
+wrap_text = true
 ```
 
+The `trailing_comma` option controls trailing commas in arrays, dictionaries, destructuring patterns, arguments and parameters. It can be `vertical` (default, only when broken into multiple lines), `always`, `never` or `preserve` (as in the source). Commas that are required, such as in the one-element array `(1,)`, are always kept.
+
+The `enum_marker` option normalizes the markers of enum items. It can be `preserve` (default), `plus` (auto-numbered `+`) or `numbered` (explicit `1.`, `2.`, and so on). With `numbered`, each item gets the number Typst gives it: explicit numbers are kept, and `+` items count up from the previous item. With `plus`, items keep their explicit numbers if they do not follow the previous item, such as a first item that does not start from 1. Regardless of this option, the bodies of list, enum and term items, including nested items, are indented by `tab_spaces`.
+
+The `blank_lines_before_heading` and `blank_lines_after_heading` options set the number of blank lines around headings in markup, and `blank_lines_around_block` sets it around block equations, raw blocks and figures that are on their own lines. When unset (default), blank lines are kept as in the source. As a blank line is a paragraph break in Typst, these options only change the number of blank lines where there is at least one, and never remove all of them, so that the layout of the document is unchanged. A value of `0` is treated as `1`. Comments on their own lines directly above an item are considered attached to it, so the blank lines before the item are added before its comments instead.
+
+```toml
+blank_lines_before_heading = 1
+blank_lines_after_heading = 1
+blank_lines_around_block = 1
+```
+
+Set `root = true` in a configuration file to stop inheriting from parent directories. Options given on the command line always take precedence. Boolean options come in pairs, such as `--tolerate-errors` and `--no-tolerate-errors`, so that either value can override the configuration file. Unknown keys in configuration files are reported as errors.
+
+Configuration files can also exclude files from formatting with gitignore-style patterns, which are relative to the directory of the configuration file. Unlike other options, the exclusions of all applicable configuration files are combined.
+
+```toml
+exclude = ["vendor/", "tests/fixtures/"]
+```
+
+```bash
+# Use a specific configuration file
+typstyle --config ci/typstyle.toml -i src/
+
+# Ignore all configuration files
+typstyle --no-config file.typ
+```
+
+The keys are the fields of the formatter configuration: `max_width`, `tab_spaces`, `indent_style`, `newline_style`, `trailing_comma`, `enum_marker`, `blank_lines_upper_bound`, `blank_lines_before_heading`, `blank_lines_after_heading`, `blank_lines_around_block`, `collapse_markup_spaces`, `reorder_import_items`, `reorder_import_statements`, `remove_unused_imports`, `wrap_text`, `sentence_abbreviations` and `tolerate_errors`.
+
+== Language Server
+
+```bash
+# Start a language server over stdio
+typstyle lsp
+```
+
+The language server provides document formatting, range formatting and on-type formatting, which is triggered when typing `}`, `]` or `)`. Configuration files are resolved for each document as for files on the command line, and style options given on the command line are applied on top of them. Documents with syntax errors are left unchanged, unless `tolerate_errors` is enabled in the configuration file or `--tolerate-errors` is given.
+
+As `lsp` is a subcommand, a file named `lsp` in the current directory must be given as `./lsp` to be formatted.
+
 == Debug Options
 
 === AST Output
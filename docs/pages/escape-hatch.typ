@@ -39,11 +39,61 @@ Use ```typ // @typstyle off``` or ```typ /* @typstyle off */``` to disable the f
 #let formatted = another_func(arg1, arg2)
 ```
 
+A comment is a directive only if `@typstyle` is its first word, so comments that merely mention it, such as ```typ // see @typstyle docs```, are ignored.
+
+== Regions
+
+To keep several items in a row verbatim, such as a block of hand-aligned definitions, enclose them between ```typ // @typstyle off``` and ```typ // @typstyle on```:
+
+```typst
+// @typstyle off
+#let   short   = 1
+#let   longer  = 22
+#let   longest = 333
+// @typstyle on
+
+#let formatted   =   4
+```
+
+Regions work in markup, including content blocks, and in code blocks. Both directives must be siblings, that is, in the same markup or code block. Everything between them is kept as is, including spaces and blank lines. Without a matching ```typ // @typstyle on```, ```typ // @typstyle off``` only applies to the next node as described above.
+
+== Disabling a Whole File
+
+Put ```typ // @typstyle off-file``` in a comment at the top level of a file, usually at its beginning, to leave the whole file unchanged. This is useful for generated files.
+
+```typst
+// @typstyle off-file
+#let   generated  =  (1,2,3)
+```
+
+== Changing Options for a Node
+
+Instead of turning the formatter off, a directive can change options for the next non-trivial syntax node, which is then formatted as if the options were set in the configuration:
+
+```typst
+// @typstyle max_width=120 wrap_text=false
+#let long-function-call = some-function(first-argument, second-argument, third)
+```
+
+The supported options are `max_width`, `tab_spaces`, `indent_style`, `trailing_comma`, `enum_marker`, `blank_lines_upper_bound`, `blank_lines_before_heading`, `blank_lines_after_heading`, `blank_lines_around_block`, `collapse_markup_spaces`, `reorder_import_items` and `wrap_text`.
+
+A directive can also disable a layout feature with `skip(...)`:
+
+- `table`: do not align the cells of tables and grids into columns. The call is formatted like any other function call.
+- `math_align`: do not align the `&` markers of equations.
+
+```typst
+// @typstyle skip(table)
+#grid(columns: 2, [a], [b], [c], [d])
+```
+
+Options and skipped features can be combined in one directive, such as ```typ // @typstyle skip(table) max_width=100```. Like ```typ // @typstyle off```, the directive applies to the first non-trivial child when placed before `Code` or `Math`.
+
 == Automatic Fallback
 
 Typstyle automatically preserves original formatting when it encounters issues:
 
-- *Syntax errors*: Code with parsing errors is left unchanged
+- *Syntax errors*: Code with parsing errors is left unchanged. With `--tolerate-errors`, only the erroneous items are kept, and the rest is formatted
 - *Complex edge cases*: Rare constructs that may downgrade formatting capabilities
 
 In these cases, you don't need an escape hatch—typstyle handles it automatically.
@@ -88,6 +138,20 @@ $
 $
 ```
 
+=== Diagnostics
+
+The CLI warns about directives that are likely mistakes, with the file and line of the comment:
+
+- Directives that have no effect, such as ```typ // @typstyle off``` followed only by comments or a blank line, or at the end of a block.
+- Unknown directives, options, option values and layout features.
+- ```typ // @typstyle on``` without a preceding ```typ // @typstyle off```.
+- ```typ // @typstyle off-file``` that is not at the top level.
+
+```
+warn: main.typ:3: directive has no effect, as it is not directly followed by an item
+```
+
+The warnings are also available in the API from `Formatter::diagnostics`.
 
 == Use Cases
 
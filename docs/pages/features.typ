@@ -82,7 +82,7 @@ Use `// @typstyle off` to disable formatting for specific code regions:
 #callout.note[
   The escape hatch only applies to the next syntax node, not the rest of the code.
 
-  There is no closing `// @typstyle on` directive.
+  To keep several items verbatim, close the region with `// @typstyle on`. Use `// @typstyle off-file` to disable formatting of the whole file.
 ]
 
 For details, please see #cross-link("/escape-hatch.typ")[Escape Hatch].
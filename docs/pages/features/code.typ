@@ -220,3 +220,32 @@ When enabled with `--reorder-import-items` (default), import items are sorted al
 ```typst
 #import "module.typ": zebra,alpha,beta,gamma
 ```
+
+=== Statement Ordering
+
+When `reorder_import_statements` is enabled in the configuration file, consecutive top-level import statements are sorted by their paths, with packages first, and duplicate imports of the same module are merged. Comments between the statements are moved with the next statement, and statements with comments, including trailing ones, are never merged. Statements are not reordered if this could change which name is imported, for example when two modules import the same name or there are wildcard imports.
+
+```typst
+/// typstyle: reorder_import_statements
+#import "utils.typ": foo
+// The template of the document
+#import "template.typ": conf
+#import "@preview/cetz:0.3.4": canvas
+#import "utils.typ": bar
+```
+
+=== Unused Imports
+
+With `--fix unused-imports`, or `remove_unused_imports` in the configuration file, import items whose names are never referenced in the file are removed, as well as import statements left without items. Wildcard imports and imports with comments are kept. Names used only in strings, such as in `eval`, are not detected.
+
+Top-level imports are visible to files importing the file, so the CLI skips the entrypoint of a package, whose imports are usually re-exported. Other files that re-export names should be excluded.
+
+```typst
+/// typstyle: remove_unused_imports
+#import "@preview/cetz:0.3.4": canvas, draw
+#import "utils.typ": foo, bar
+#import "unused.typ": baz
+
+#canvas(draw.line((0, 0), (1, 1)))
+#foo
+```
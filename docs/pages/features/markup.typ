@@ -103,10 +103,38 @@ End
 ````
 
 
+=== Sentence per Line
+
+With `--wrap-text=sentence` or `wrap_text = "sentence"` in the configuration file, typstyle puts each sentence on its own line instead, and joins the lines within a sentence. This is also known as semantic line breaks, and makes diffs of prose easier to review. The same rules as above decide where lines can be broken safely.
+
+```typst
+/// typstyle: wrap_text=sentence
+This is the first sentence. This one was hard-wrapped
+by the author. See Fig. 2, e.g. here. Is it short? Yes!
+```
+
+Words ending with `.`, `!` or `?` end a sentence, except single initials such as `J.` and the abbreviations in the `sentence_abbreviations` option, which defaults to common English ones such as `e.g.`, `i.e.`, `etc.` and `Fig.`.
+
+=== Unwrapping
+
+With `--wrap-text=unwrap` or `wrap_text = "unwrap"`, typstyle does the opposite of wrapping, and joins the lines of each paragraph into a single line. This suits editors that soft-wrap long lines. Paragraph breaks, list items, linebreaks (`\`) and other breaks that must be kept by the rules above are preserved.
+
+```typst
+/// typstyle: wrap_text=unwrap
+This paragraph was hard-wrapped
+by an editor that does not soft-wrap.
+A linebreak \
+is kept.
+- List items
+  are joined too.
+```
+
 === Multilingual Text Support
 
 typstyle measures Unicode width and will not break between words if no space exists in the original text.
 
+Typst renders a linebreak between two CJK characters as a space, so typstyle never breaks lines between CJK characters, even if there is a line breaking opportunity. For the same reason, existing linebreaks between CJK characters are kept instead of being joined with a space.
+
 ```typst
 /// typstyle: wrap_text, max_width=40
 这是一个中文段落，包含链接 https://typst.app/ 和*强调文本*。
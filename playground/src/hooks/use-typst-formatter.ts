@@ -28,7 +28,7 @@ export function useTypstFormatter(
       blank_lines_upper_bound: 2, // Default value, not exposed in UI
       collapse_markup_spaces: formatOptions.collapseMarkupSpaces,
       reorder_import_items: formatOptions.reorderImportItems,
-      wrap_text: formatOptions.wrapText,
+      wrap_text: formatOptions.wrapText ? "fill" : "off",
     };
 
     try {
@@ -0,0 +1,11 @@
+/// typstyle: reorder_import_statements
+#import "a.typ": x
+#import "a.typ": y // important note
+#import "b.typ": p /* block note */
+#import "b.typ": q
+#import "c.typ": m
+#import "c.typ": m // duplicate with a note
+#import "d.typ": u
+#import "d.typ": v
+
+Text.
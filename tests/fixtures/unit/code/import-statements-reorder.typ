@@ -0,0 +1,22 @@
+/// typstyle: reorder_import_statements
+#import "@preview/cetz:0.3.4": draw
+#import "utils.typ": foo, bar
+// The template of the document
+#import "template.typ": conf
+#import "@preview/cetz:0.3.4": canvas
+#import "utils.typ": baz, foo
+#import "@preview/fletcher:0.5.7" as fletcher: diagram, node // inline comment
+#import "utils.typ": (
+  qux,
+)
+#import "lib.typ"
+#import "lib.typ"
+
+#import "b.typ": x
+#import "a.typ": y
+
+= Title
+#import "z.typ": z
+#import "y.typ": y2
+
+Text.
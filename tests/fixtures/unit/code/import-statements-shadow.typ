@@ -0,0 +1,13 @@
+/// typstyle: reorder_import_statements
+// Later imports shadow earlier ones, so they are not reordered.
+#import "b.typ": f
+#import "a.typ": f
+
+#import "d.typ": *
+#import "c.typ": g
+
+#import "f.typ": h
+// Comments on merged imports keep them separate.
+#import "e.typ": i
+#import "f.typ": j
+#import "f.typ": k
@@ -0,0 +1,32 @@
+/// typstyle: remove_unused_imports
+#import "@preview/cetz:0.3.4": canvas, draw
+#import "utils.typ": foo, bar as baz, unused
+#import "unused.typ": a, b
+
+#import "all.typ": *
+#import "module.typ" as module: c
+#import "comment.typ": d // kept with comment
+#import "nested.typ": e, f.g
+#import "math.typ": (
+  alpha2,
+  beta2,
+  gamma2,
+)
+
+= Title
+#canvas(draw.line((0, 0), (1, 1)))
+#foo #baz #g
+
+$alpha2 + gamma2$
+
+#let f() = {
+  import "local.typ": x, y
+  import "local.typ": z; x
+  import "other.typ": w // comment
+}
+
+#[
+  #import "inner.typ": i
+  #import "inner.typ": j
+  #j
+]
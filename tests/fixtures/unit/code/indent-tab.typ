@@ -0,0 +1,19 @@
+/// typstyle: indent_style=tab
+#let f(x, y) = {
+  let sum = (x, y, 1, 2, 3).map(v => calc.pow(v, 2)).fold(0, (acc, v) => acc + v)
+  if sum > 10 {
+    (first: x, second: y, third: "a longer string value", fourth: sum)
+  } else {
+        ```rust
+        fn main() {
+            println!("{}", 1);
+        }
+        ```
+  }
+}
+
+#figure(table(columns: 3, [a], [b], [c], [long content in a cell], [more content here], [end]), caption: [Caption])
+
+- item
+  - nested item with a #text(fill: red)[styled text that has some length to it]
+    + enumerated
@@ -0,0 +1,32 @@
+/// typstyle: trailing_comma=always
+// Commas are added to flat lists.
+#let pair = (1, 2)
+#let dict = (a: 1, b: 2)
+#let single = (1,)
+#let single-dict = (a: 1)
+#let empty = ()
+#let empty-dict = (:)
+#let spread = (..pair)
+#let nested = ((1, 2), (a: (b: 3)))
+
+// Commas are added to long lists, whether broken or not.
+#let long = (aaaaaaaaaaaaaaa, bbbbbbbbbbbbbbbbbbbbb, cccccccccccccccccc, ddddddddddddd)
+
+// Destructuring patterns, parameters and closures.
+#let (a, b) = (1, 2)
+#let (single,) = (1,)
+#let ((a, b), c) = ((1, 2), 3)
+#let (first, ..rest) = (1, 2, 3)
+#let (key: value) = (key: 1)
+#for (key, value) in dict {}
+#let first-of((a, b)) = a
+#let f(x, y) = x + y
+#let g(x, y: 1) = x
+#let h = x => x
+#let k = (x, y) => x
+
+// Arguments, including those followed by content blocks.
+#f(1, 2)
+#f(x)[body]
+#f()[body]
+#f(aaaaaaaaaaaaaaaaaaaaaaaa, bbbbbbbbbbbbbbbbbbbbbbbbbbbbbb, cccccccccccccccccccccccc)
@@ -0,0 +1,42 @@
+/// typstyle: trailing_comma=never
+// Commas are removed from flat lists, except required ones.
+#let pair = (1, 2,)
+#let dict = (a: 1, b: 2,)
+#let single = (1,)
+#let single-long = (aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa,)
+#let single-dict = (a: 1,)
+#let spread = (..pair,)
+
+// Commas are removed from broken lists.
+#let long = (
+  aaaaaaaaaaaaaaa,
+  bbbbbbbbbbbbbbbbbbbbb,
+  cccccccccccccccccc,
+  ddddddddddddd,
+)
+#let commented = (
+  1,
+  2, // last
+)
+
+// Destructuring patterns, parameters and closures.
+#let (a, b,) = (1, 2)
+#let (single,) = (1,)
+#let ((a, b,), c,) = ((1, 2,), 3,)
+#let (first, ..rest,) = (1, 2, 3)
+#let first-of((a, b,)) = a
+#for (key, value,) in dict {}
+#let f(x, y,) = x + y
+#let k = (x, y,) => x
+
+// Arguments.
+#f(1, 2,)
+#f(x,)[body]
+#f(
+  aaaaaaaaaaaaaaaaaaaaaaaa,
+  (a: 1, b: 2),
+  (c: 3, d: 4),
+  (e: 5, f: 6),
+  (g: 7, h: 8),
+)
+#table(columns: 2, [a], [b],)
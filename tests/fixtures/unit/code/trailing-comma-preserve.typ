@@ -0,0 +1,31 @@
+/// typstyle: trailing_comma=preserve
+// Flat lists keep their commas or the lack of them.
+#let with = (1, 2,)
+#let without = (1, 2)
+#let dict-with = (a: 1, b: 2,)
+#let dict-without = (a: 1, b: 2)
+#let single = (1,)
+
+// Long lists keep the comma of the source, whether broken or not.
+#let long-with = (aaaaaaaaaaaaaaa, bbbbbbbbbbbbbbbbbbbbb, cccccccccccccccccc, ddddddddddddd,)
+#let long-without = (aaaaaaaaaaaaaaa, bbbbbbbbbbbbbbbbbbbbb, cccccccccccccccccc, ddddddddddddd)
+
+// Lists broken in the source keep their comma.
+#let broken-with = (
+  1,
+  2,
+)
+#let broken-without = (
+  1,
+  2
+)
+
+// Destructuring patterns, parameters and arguments.
+#let (a, b,) = (1, 2)
+#let (a, b) = (1, 2)
+#let f(x, y,) = x + y
+#let f(x, y) = x + y
+#f(1, 2,)
+#f(1, 2)
+#f(aaaaaaaaaaaaaaaaaaaaaaaa, bbbbbbbbbbbbbbbbbbbbbbbbbbbbbb, cccccccccccccccccccccccc,)
+#f(aaaaaaaaaaaaaaaaaaaaaaaa, bbbbbbbbbbbbbbbbbbbbbbbbbbbbbb, cccccccccccccccccccccccc)
@@ -0,0 +1,53 @@
+/// typstyle: blank_lines_before_heading=2 blank_lines_after_heading=1 blank_lines_around_block=1
+= Introduction
+Some text right after the heading.
+== Details
+
+
+More text.
+$ x + y $
+After the equation.
+// A comment attached to the figure
+#figure(rect(), caption: [A figure]) <fig:rect>
+After the figure.
+```rust
+fn main() {}
+```
+
+
+
+Final paragraph.
+#[
+  = Nested
+  Text in content.
+]
+Text before commented heading.
+// First comment about the heading
+/* Second comment */
+= Commented heading
+// A comment after the heading
+Text after the heading.
+// A detached comment
+
+$ a + b $
+Text before a heading after blank lines.
+
+
+
+= Spaced heading
+
+
+Text after blank lines.
+
+$ c + d $
+
+
+
+Text after blank lines.
+
+
+// A comment attached to the raw block
+```rust
+fn main() {}
+```
+Text without a blank line.
@@ -0,0 +1,12 @@
+/// typstyle: blank_lines_before_heading=0 blank_lines_after_heading=0
+= Title
+
+Paragraph one.
+
+== Section
+
+
+Paragraph two.
+- item
+
+= Another
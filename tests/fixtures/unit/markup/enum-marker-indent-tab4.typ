@@ -0,0 +1,29 @@
+/// typstyle: enum_marker=plus tab_spaces=4
+Over-indented bodies:
+- Outer
+        - Nested
+              - Innermost
+                text of the innermost item
+        - Nested again
++ First
+       + Inner
+          text of the inner item
+
+       + Inner after a blank line
+
+Under-indented bodies:
+- Outer
+ - Nested
+  - Innermost
+   text of the innermost item
+ continued outer text
++ First
+ + Inner
+   + Innermost
+ - A list in an enum
+
+Mixed in content: #[
+  + One
+         - Deep
+   - Shallow
+]
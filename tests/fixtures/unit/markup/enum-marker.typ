@@ -0,0 +1,68 @@
+/// typstyle: enum_marker=numbered
+Automatically numbered:
++ Preparations
++ Analysis
++ Conclusions
+
+Manually numbered:
+2. What is the first step?
+3. I am confused.
++  Moving on ...
+
+Separated by blank lines and comments:
+1. First
+
+// A comment
++ Second
+
+3. Third
+
+Nested:
+- A list
+  + Inner first
+    + Innermost
+  2. Inner second
++ Back to the outer enum
+2. Last
+
+In content: #[
+  1. One
+  + Two
+]
+
+Explicit start numbers:
+5. Five
++ Six
++ Seven
+
+0. Zero
++ One
+
+Explicit numbers in a sequence:
+1. One
+1. Still one
++ Two
+10. Ten
++ Eleven
+
+A sequence interrupted by text:
+3. Three
++ Four
+Text
++ One again
+
+Nested mixed lists:
+4. Four
+  - A list
+  - in an enum
+    7. Seven
+    + Eight
+  + One, nested
+  + Two, nested
++ Five
+- A list between enums
++ One
+/ Term: description
+  + One in a term
+  3. Three in a term
++ One after a term
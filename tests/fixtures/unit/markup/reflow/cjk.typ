@@ -0,0 +1,12 @@
+/// typstyle: wrap_text
+Typst 是为学术写作而生的基于标记的排版系统。它的设计目标是成为功能强大的 LaTeX 的替代品，或者是更加简单的 Word 和 Google Docs 的替代品。我们对 Typst 的长期目标是构建一个能够适应多种场景的排版系统。
+
+日本語の文章も、句読点の前では改行されません。「かぎ括弧」の中も正しく扱われます。これはとても長い文なので、行の幅に合わせて折り返されるはずです。
+
+English words in a CJK paragraph like 排版系统 and Typst are kept, and CJK punctuation such as「引号」，逗号。are not broken before.
+
+中文段落
+被手动折行了，
+这些换行会被保留，
+but a linebreak next to English words
+可以被合并。
@@ -0,0 +1,20 @@
+/// typstyle: wrap_text=sentence
+This is the first sentence. This is the second one, which is rather long and was
+hard-wrapped by the author. Is this a question? Yes!
+
+Abbreviations such as e.g. this one, or Fig. 3 and J. Doe, do not end sentences.
+Numbers like 3.14 do not either.
+The end of a sentence (in parentheses.) And "quoted." Next.
+
+A sentence ends with *strong* text. Then #link("https://typst.app")[a link]. And `raw`. Done.
+
+A line with a linebreak \
+continues here. Count to 2. 3 is next.
+Do not start a line with markers. - is a dash. = is equal. 1. is a number.
+
+- A list item. With two sentences.
+- Another item
+  continued here.
+
+$ x + y $
+Text after an equation. Right.
@@ -0,0 +1,13 @@
+/// typstyle: wrap_text=unwrap
+中文段落
+被手动折行了，
+这些行会被合并成一行。
+
+日本語の文章も
+一行に
+まとめられます。
+
+A linebreak next to English words
+可以被合并，
+and CJK text with English
+is joined as well.
@@ -0,0 +1,27 @@
+/// typstyle: wrap_text=unwrap
+This paragraph was hard-wrapped
+by an editor that does not soft-wrap,
+and should become a single line.
+
+A linebreak \
+is kept. So are
+- list items that
+  span several lines,
+- and other items.
+
++ Enum items
+  are joined too.
+
+/ Term: with a description
+  over two lines.
+
+$ x + y $
+Text after an equation
+is joined.
+#figure(rect(), caption: [A figure
+  with a caption]) <fig>
+// A comment
+ends the line.
+
+中文段落
+被手动折行了。
@@ -0,0 +1,37 @@
+/// typstyle: tolerate_errors
+
+#let good  =  (1,2,3)
+#let bad = 1 +
+
+#{
+  let a  =  1
+  let b =
+  let c  =  f(a,b)
+  (1,
+}
+
+#[
+  - item   one
+  #let x = ;
+  #let y  =  g(x,y)
+]
+
+#f(a,   [ #let = ])
+
+$ x^  $ and $x  +  y$
+
+Text ] with a stray bracket and #h(x,y).
+
+a *b
+
+c
+
+text *
+
+*strong*
+
+- item _with   emphasis
+
+  continued
+
+#let after  =  (a:1,b:2)
@@ -0,0 +1,34 @@
+// @typstyle skip(table)
+#table(columns: 3, [Name], [Age], [City], [Alice], [30], [Paris], [Bob], [25], [London])
+
+#table(columns: 3, [Name], [Age], [City], [Alice], [30], [Paris], [Bob], [25], [London])
+
+// @typstyle max_width=120
+#let long-function-call = some-function(first-argument, second-argument, third-argument, fourth)
+
+#let long-function-call = some-function(first-argument, second-argument, third-argument, fourth)
+
+#let f() = {
+  // @typstyle max_width=40 tab_spaces=4
+  let config = (name: "typstyle", version: "0.13", features: ("format", "check"))
+  config
+}
+
+/* @typstyle skip(math_align) */
+$
+  a &= b + c \
+  d + e &= f
+$
+
+$
+  a &= b + c \
+  d + e &= f
+$
+
+// @typstyle trailing_comma=always skip(table) unknown(x) bogus
+#grid(columns: 2, [a], [b], [c], [d])
+
+#{
+  let value = /* @typstyle max_width=50 */ f(first-argument, second, third)
+  let value = f(first-argument, second, third, fourth-argument, fifth)
+}
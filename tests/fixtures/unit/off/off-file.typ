@@ -0,0 +1,9 @@
+// Generated file, do not format.
+// @typstyle off-file
+
+#let   table-data  =  (
+  (1,   2,   3),
+  (4,  5,  6),
+)
+
+=   Heading
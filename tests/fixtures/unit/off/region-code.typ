@@ -0,0 +1,17 @@
+#let matrix = {
+  let   x  =  1
+  // @typstyle off
+  let   a11 = 1;   let a12 = 0
+  let   a21 = 0;   let a22 = 1
+  // @typstyle on
+  let   y  =  2
+  (x,y,a11,a12,a21,a22)
+}
+
+#{
+  // @typstyle off
+  let   only   =   (1,
+        2)
+  /* @typstyle on */ let   z  =  3
+  only
+}
@@ -0,0 +1,24 @@
+= Aligned definitions
+
+// @typstyle off
+#let   short      = 1
+#let   longer     = 22
+#let   longest    = 333
+
+Text   kept    as    is.
+// @typstyle on
+#let   formatted  = 4
+
+Before /* @typstyle off */ #(1+2)   and   #(3+4) /* @typstyle on */ after   #(5+6).
+
+#[
+  // @typstyle off
+  #let  a  =  (1,2)
+    #let  b  =  (3,4)
+  // @typstyle on
+  #let  c  =  (5,6)
+]
+
+// @typstyle off
+#let   single   =   1
+#let   next   =   2
@@ -85,10 +85,36 @@ fn parse_directives(content: &str) -> Result<Options, Failed> {
             "relax_convergence" => {
                 options.relax_convergence = value.and_then(|v| v.parse().ok()).unwrap_or(1)
             }
+            "tab_spaces" => {
+                config.tab_spaces = value.unwrap_or_default().parse()?;
+            }
+            "indent_style" => {
+                config.indent_style = value.unwrap_or_default().parse()?;
+            }
+            "blank_lines_before_heading" => {
+                config.blank_lines_before_heading = value.and_then(|v| v.parse().ok());
+            }
+            "blank_lines_after_heading" => {
+                config.blank_lines_after_heading = value.and_then(|v| v.parse().ok());
+            }
+            "blank_lines_around_block" => {
+                config.blank_lines_around_block = value.and_then(|v| v.parse().ok());
+            }
+            "enum_marker" => {
+                config.enum_marker = value.unwrap_or_default().parse()?;
+            }
+            "trailing_comma" => {
+                config.trailing_comma = value.unwrap_or_default().parse()?;
+            }
             "reorder_import_items" => config.reorder_import_items = value != Some("false"),
+            "reorder_import_statements" => {
+                config.reorder_import_statements = value != Some("false")
+            }
+            "remove_unused_imports" => config.remove_unused_imports = value != Some("false"),
+            "tolerate_errors" => config.tolerate_errors = value != Some("false"),
             "wrap_text" => {
-                config.wrap_text = value != Some("false");
-                config.collapse_markup_spaces |= config.wrap_text;
+                config.wrap_text = value.unwrap_or("fill").parse()?;
+                config.collapse_markup_spaces |= config.wrap_text.is_enabled();
             }
             _ => return Err(format!("unknown directive: {key}").into()),
         }
@@ -34,7 +34,27 @@ pub fn collect_tests() -> Vec<Trial> {
         ),
     ];
 
+    let multi_cases = [
+        Testcase::new("partial/indenta.typ", [80..85, 200..300, 867..869]),
+        Testcase::new("partial/indenta.typ", [387..713, 400..420, 500..600]),
+        Testcase::new("partial/erroneous.typ", [17..20, 25..30]),
+        Testcase::new("partial/erroneous.typ", [17..20, 36..40]),
+    ];
+
     let root = fixtures_dir();
+    let multi_trials = multi_cases.into_iter().map(|case| {
+        let path = root.join(case.path);
+        let name = case
+            .ranges
+            .iter()
+            .map(|rng| format!("{}..{}", rng.start, rng.end))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Trial::test(format!("{} - {name}", case.path), move || {
+            check_multi_snapshot(&path, &case.ranges)
+        })
+        .with_kind("partial")
+    });
     cases
         .into_iter()
         .flat_map(|case| {
@@ -48,6 +68,7 @@ pub fn collect_tests() -> Vec<Trial> {
                 .with_kind("partial")
             })
         })
+        .chain(multi_trials)
         .collect()
 }
 
@@ -90,6 +111,62 @@ fn check_snapshot(path: &Path, range: Range<usize>) -> Result<(), Failed> {
     Ok(())
 }
 
+fn check_multi_snapshot(path: &Path, ranges: &[Range<usize>]) -> Result<(), Failed> {
+    let source = read_source(path)?;
+
+    let mut settings = insta::Settings::clone_current();
+    settings.set_prepend_module_to_snapshot(false);
+    settings.set_omit_expression(true);
+    settings.set_snapshot_path(path.parent().unwrap().join("snap"));
+    settings.set_input_file(path);
+
+    let snap_name = format!(
+        "{}-{}",
+        path.file_name().unwrap().to_str().unwrap(),
+        ranges
+            .iter()
+            .map(|rng| format!("{}_{}", rng.start, rng.end))
+            .collect::<Vec<_>>()
+            .join("-")
+    );
+    let mut info: Vec<(Content, Content)> = vec![(
+        "ranges".into(),
+        Content::Seq(ranges.iter().map(range_to_content).collect()),
+    )];
+
+    match Typstyle::default().format_source_ranges(&source, ranges.iter().cloned()) {
+        Ok(edits) => {
+            info.push((
+                "range_nodes".into(),
+                Content::Seq(
+                    edits
+                        .iter()
+                        .map(|edit| range_to_content(&edit.range))
+                        .collect(),
+                ),
+            ));
+            settings.set_raw_info(&Content::Map(info));
+
+            let mut formatted = source.text().to_string();
+            for edit in edits.iter().rev() {
+                formatted.replace_range(edit.range.clone(), &edit.replacement);
+            }
+            settings.bind(|| {
+                insta::assert_snapshot!(snap_name, formatted);
+            });
+        }
+        Err(_) => {
+            info.push(("erroneous".into(), true.into()));
+            settings.set_raw_info(&Content::Map(info));
+
+            settings.bind(|| {
+                insta::assert_snapshot!(snap_name, "");
+            });
+        }
+    }
+    Ok(())
+}
+
 fn range_to_content(range: &Range<usize>) -> Content {
     Content::Map(vec![
         ("start".into(), (range.start as u64).into()),
@@ -5,7 +5,7 @@ use libtest_mimic::{Failed, Trial};
 use serde::Deserialize;
 use typst_syntax::Source;
 use typstyle_consistency::{ErrorSink, FormattedSources, FormatterHarness};
-use typstyle_core::{Config, Typstyle};
+use typstyle_core::{Config, Typstyle, WrapText};
 
 use crate::common::{fixtures_dir, test_dir};
 
@@ -85,7 +85,7 @@ fn run_testcase(testcase: Testcase) -> anyhow::Result<()> {
             name: "reflow",
             config: Config {
                 reorder_import_items: true,
-                wrap_text: true,
+                wrap_text: WrapText::Fill,
                 ..Default::default()
             },
         },
@@ -109,7 +109,7 @@ fn check_snapshot(path: &Path, width: usize) -> Result<(), Failed> {
     }
     settings.bind(|| {
         let snap_name = format!("{}-{width}", path.file_name().unwrap().to_str().unwrap());
-        if source.root().erroneous() {
+        if source.root().erroneous() && !cfg.tolerate_errors {
             insta::assert_snapshot!(snap_name, "");
         } else {
             cfg.max_width = width;
@@ -130,7 +130,8 @@ fn check_snapshot(path: &Path, width: usize) -> Result<(), Failed> {
 fn check_convergence(path: &Path, width: usize) -> Result<(), Failed> {
     let (source, opt) = read_source_with_options(path)?;
     let mut cfg = opt.config;
-    if source.root().erroneous() {
+    let was_erroneous = source.root().erroneous();
+    if was_erroneous && !cfg.tolerate_errors {
         return Ok(());
     }
 
@@ -139,7 +140,7 @@ fn check_convergence(path: &Path, width: usize) -> Result<(), Failed> {
     let mut first_pass = t.format_source(source).render()?;
     for i in 0..=opt.relax_convergence {
         let new_source = Source::detached(&first_pass);
-        if new_source.root().erroneous() {
+        if new_source.root().erroneous() && !was_erroneous {
             panic!(
                 "the source becomes erroneous after {} iterations:\n{:#?}",
                 i + 1,